@@ -0,0 +1,23 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rlifesrc_lib::{Config, Status};
+
+/// Finds smaller and smaller still lives in a 20x20 world,
+/// until it is proved that there is no smaller one.
+fn still_life(c: &mut Criterion) {
+    let config = Config::new(20, 20, 1)
+        .set_max_cell_count(Some(100))
+        .set_reduce_max(true);
+    let mut group = c.benchmark_group("still life");
+    group.sample_size(10);
+    group.bench_function("20x20", |b| {
+        b.iter(|| {
+            let mut search = config.world().unwrap();
+            while search.search(None) == Status::Found {}
+            search.cell_count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, still_life);
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rlifesrc_lib::Config;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The system allocator, counting the number of allocations.
+struct CountingAllocator;
+
+/// Number of allocations and reallocations so far.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Creates and searches 100 16x16 worlds in sequence.
+fn searches(config: &Config) {
+    for _ in 0..100 {
+        let mut search = config.world().unwrap();
+        search.search(None);
+    }
+}
+
+/// Creates and searches many small worlds in sequence, as in
+/// `Config::search_increasing` and `Config::sweep_rules`,
+/// where creating the worlds takes most of the time.
+///
+/// Also prints the number of allocations, which criterion does not measure.
+fn worlds(c: &mut Criterion) {
+    let config = Config::new(16, 16, 1);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    searches(&config);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("100 16x16 searches: {} allocations", allocations);
+
+    c.bench_function("100 16x16 searches", |b| b.iter(|| searches(&config)));
+}
+
+criterion_group!(benches, worlds);
+criterion_main!(benches);
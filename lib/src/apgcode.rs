@@ -0,0 +1,162 @@
+#![cfg(feature = "apgcode")]
+//! Adam P. Goucher's [apgcodes](https://conwaylife.com/wiki/Apgcode).
+
+use crate::{
+    rules::Rule,
+    world::{Point, World, ISOMETRIES},
+};
+use std::collections::HashSet;
+
+/// Digits used by the extended Wechsler format.
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// A set of living cells in one generation.
+type Pattern = Vec<Point>;
+
+/// Translates a pattern so that its bounding box starts at `(0, 0)`,
+/// and sorts the cells.
+///
+/// Returns the translated pattern and the amount of translation.
+fn normalize(mut pattern: Pattern) -> (Pattern, Point) {
+    let min_x = pattern.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = pattern.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    for cell in pattern.iter_mut() {
+        cell.0 -= min_x;
+        cell.1 -= min_y;
+    }
+    pattern.sort_unstable();
+    (pattern, (min_x, min_y))
+}
+
+/// Pushes a run of `0`s, abbreviated as `w`, `x` or `y` followed by a digit.
+fn push_zeros(str: &mut String, mut zeros: usize) {
+    while zeros > 0 {
+        let n = zeros.min(39);
+        match n {
+            1 => str.push('0'),
+            2 => str.push('w'),
+            3 => str.push('x'),
+            _ => {
+                str.push('y');
+                str.push(DIGITS[n - 4] as char);
+            }
+        }
+        zeros -= n;
+    }
+}
+
+/// Encodes a normalized pattern in the extended Wechsler format.
+fn wechsler(pattern: &[Point]) -> String {
+    let width = pattern.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+    let height = pattern.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+    let cells: HashSet<_> = pattern.iter().collect();
+    let mut strips = Vec::new();
+    for y0 in (0..height).step_by(5) {
+        let mut strip: Vec<u8> = (0..width)
+            .map(|x| {
+                (0..5)
+                    .filter(|i| cells.contains(&(x, y0 + i)))
+                    .map(|i| 1 << i)
+                    .sum::<usize>()
+            })
+            .map(|n| DIGITS[n])
+            .collect();
+        while strip.last() == Some(&b'0') {
+            strip.pop();
+        }
+        let mut str = String::new();
+        let mut zeros = 0;
+        for c in strip {
+            if c == b'0' {
+                zeros += 1;
+            } else {
+                push_zeros(&mut str, zeros);
+                zeros = 0;
+                str.push(c as char);
+            }
+        }
+        push_zeros(&mut str, zeros);
+        strips.push(str);
+    }
+    strips.join("z")
+}
+
+impl<'a, R: Rule> World<'a, R> {
+    /// The living cells in generation `t`, relative to the background.
+    ///
+    /// `t` can be outside of the period. Returns `None` if some cell
+    /// is unknown.
+    fn living_cells(&self, t: isize) -> Option<Pattern> {
+        let r = t.rem_euclid(self.config.period);
+        let mut pattern = Vec::new();
+        for x in 0..self.config.width {
+            for y in 0..self.config.height {
+                let cell = self.find_cell((x, y, r)).unwrap();
+                if cell.state.get()? != cell.background {
+                    let (new_x, new_y, _) = self.config.translate((x, y, 2 * r - t));
+                    pattern.push((new_x, new_y));
+                }
+            }
+        }
+        Some(pattern)
+    }
+
+    /// Returns the [apgcode](https://conwaylife.com/wiki/Apgcode)
+    /// of the pattern.
+    ///
+    /// The prefix (`xs`, `xp` or `xq`) and the period are determined from
+    /// `period`, `dx`, `dy` and `transform` of the configuration. If the
+    /// actual period of the pattern is smaller than the period of the
+    /// search, the actual period is used instead.
+    ///
+    /// Returns `None` if the pattern contains unknown cells or is empty.
+    /// Generations rules are not supported yet.
+    pub fn apgcode(&self) -> Option<String> {
+        if R::IS_GEN {
+            return None;
+        }
+
+        // The pattern returns to itself after at most `4 * period`
+        // generations, with a possible translation.
+        let full_period = 4 * self.config.period;
+        let phases = (0..=full_period)
+            .map(|t| self.living_cells(t).map(normalize))
+            .collect::<Option<Vec<_>>>()?;
+        if phases[0].0.is_empty() {
+            return None;
+        }
+
+        let (period, moving) = (1..=full_period)
+            .filter(|p| full_period % p == 0)
+            .find_map(|p| {
+                let (pattern, offset) = &phases[p as usize];
+                if *pattern == phases[0].0 {
+                    Some((p, *offset != phases[0].1))
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+
+        let prefix = if moving {
+            format!("xq{}", period)
+        } else if period == 1 {
+            format!("xs{}", phases[0].0.len())
+        } else {
+            format!("xp{}", period)
+        };
+
+        let code = phases[..period as usize]
+            .iter()
+            .flat_map(|(pattern, _)| {
+                ISOMETRIES.iter().map(move |f| {
+                    let (pattern, _) = normalize(pattern.iter().copied().map(f).collect());
+                    wechsler(&pattern)
+                })
+            })
+            .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+            .unwrap();
+
+        Some(format!("{}_{}", prefix, code))
+    }
+}
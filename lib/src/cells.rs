@@ -15,7 +15,8 @@ use serde::{Deserialize, Serialize};
 ///
 /// During the search, the state of a cell is represented by `Option<State>`,
 /// where `None` means that the state of the cell is unknown.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// `CellState` gives the same information without the `Option`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct State(pub usize);
 
@@ -36,6 +37,47 @@ impl Not for State {
     }
 }
 
+/// The state of a cell, known or unknown.
+///
+/// The search itself represents this by `Option<State>`, where `None`
+/// means unknown. This enum spells out the cases for code that displays
+/// or exports the cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellState {
+    /// A dead cell, i.e., `DEAD`.
+    Dead,
+    /// A living cell, i.e., `ALIVE`.
+    Alive,
+    /// A dying cell of a Generations rule.
+    ///
+    /// The number is the `State`, starting from 2.
+    Dying(usize),
+    /// A cell whose state is not yet known.
+    Unknown,
+}
+
+impl From<Option<State>> for CellState {
+    fn from(state: Option<State>) -> Self {
+        match state {
+            Some(DEAD) => CellState::Dead,
+            Some(ALIVE) => CellState::Alive,
+            Some(State(i)) => CellState::Dying(i),
+            None => CellState::Unknown,
+        }
+    }
+}
+
+impl From<CellState> for Option<State> {
+    fn from(state: CellState) -> Self {
+        match state {
+            CellState::Dead => Some(DEAD),
+            CellState::Alive => Some(ALIVE),
+            CellState::Dying(i) => Some(State(i)),
+            CellState::Unknown => None,
+        }
+    }
+}
+
 /// The coordinates of a cell.
 ///
 /// `(x-coordinate, y-coordinate, time)`.
@@ -78,16 +120,38 @@ pub struct LifeCell<'a, R: Rule> {
     ///
     /// The cell in the next generation at the same position.
     pub(crate) succ: Option<CellRef<'a, R>>,
-    /// The eight cells in the neighborhood.
-    pub(crate) nbhd: [Option<CellRef<'a, R>>; 8],
+    /// The cells in the neighborhood, in the order given by `Rule::nbhd`.
+    pub(crate) nbhd: Vec<Option<CellRef<'a, R>>>,
     /// The cells in the same generation that must has the same state
     /// with this cell because of the symmetry.
     pub(crate) sym: Vec<CellRef<'a, R>>,
 
-    /// Whether the cell is on the first row or column.
+    /// Whether the cell is on the front, i.e., the first `front_lines`
+    /// rows or columns.
     ///
     /// Here the choice of row or column depends on the search order.
     pub(crate) is_front: bool,
+
+    /// Whether the cell is the representative of its orbit under the
+    /// symmetry, i.e., it comes first among the symmetric cells
+    /// in the same generation.
+    ///
+    /// Used when `count_mode` of the configuration is
+    /// `CountMode::OrbitRepresentatives`.
+    pub(crate) is_orbit_rep: bool,
+
+    /// The position of the cell in the `search_list` of the world.
+    ///
+    /// `None` for the cells outside of the search range.
+    pub(crate) search_pos: Option<usize>,
+
+    /// The activity of the cell, used by `DecisionHeuristic::Vsids`.
+    pub(crate) activity: Cell<f64>,
+
+    /// The last state of the cell before it is cleared in the backtracking.
+    ///
+    /// Used when `phase_saving` of the configuration is set.
+    pub(crate) phase: Cell<Option<State>>,
 }
 
 impl<'a, R: Rule> LifeCell<'a, R> {
@@ -95,17 +159,21 @@ impl<'a, R: Rule> LifeCell<'a, R> {
     /// descriptor says that all neighboring cells also have the same state.
     ///
     /// `first_gen` and `first_col` are set to `false`.
-    pub(crate) fn new(coord: Coord, background: State, succ_state: State) -> Self {
+    pub(crate) fn new(coord: Coord, background: State, succ_state: State, rule: &R) -> Self {
         LifeCell {
             coord,
             background,
             state: Cell::new(Some(background)),
-            desc: Cell::new(R::new_desc(background, succ_state)),
+            desc: Cell::new(rule.new_desc(background, succ_state)),
             pred: Default::default(),
             succ: Default::default(),
             nbhd: Default::default(),
             sym: Default::default(),
             is_front: false,
+            is_orbit_rep: true,
+            search_pos: None,
+            activity: Cell::new(0.0),
+            phase: Cell::new(None),
         }
     }
 
@@ -135,6 +203,11 @@ pub struct CellRef<'a, R: Rule> {
 }
 
 impl<'a, R: Rule> CellRef<'a, R> {
+    /// The state of the cell, with unknown cells as `CellState::Unknown`.
+    pub fn cell_state(self) -> CellState {
+        self.state.get().into()
+    }
+
     /// Updates the neighborhood descriptors of all neighbors and the predecessor
     /// when the state of one cell is changed.
     ///
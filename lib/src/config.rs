@@ -1,16 +1,20 @@
 //! World configuration.
 
 use crate::{
-    cells::Coord,
+    cells::{Coord, State},
     error::Error,
-    rules::{Life, LifeGen, NtLife, NtLifeGen, Rule},
+    rules::{validate_rule_string, with_rule, Rule, WithRule},
+    search::Status,
     traits::Search,
     world::World,
 };
 use derivative::Derivative;
 use std::{
     cmp::Ordering,
-    fmt::{self, Debug, Formatter},
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    mem,
+    ops::Mul,
     str::FromStr,
 };
 
@@ -36,7 +40,7 @@ use serde::{Deserialize, Serialize};
 /// The symbol after it is the axis of reflection.
 ///
 /// Some of the transformations are only valid when the world is square.
-#[derive(Clone, Copy, Derivative, PartialEq, Eq)]
+#[derive(Clone, Copy, Derivative, PartialEq, Eq, Hash)]
 #[derivative(Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Transform {
@@ -75,12 +79,28 @@ pub enum Transform {
     FlipAntidiag,
 }
 
+/// All the transformations.
+const TRANSFORMS: [Transform; 8] = [
+    Transform::Id,
+    Transform::Rotate90,
+    Transform::Rotate180,
+    Transform::Rotate270,
+    Transform::FlipRow,
+    Transform::FlipCol,
+    Transform::FlipDiag,
+    Transform::FlipAntidiag,
+];
+
+/// Parses a transformation.
+///
+/// Surrounding whitespaces are ignored, and lowercase letters are accepted.
+/// `I` is an alias of `Id`.
 impl FromStr for Transform {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Id" => Ok(Transform::Id),
+        match s.trim().to_uppercase().as_str() {
+            "ID" | "I" => Ok(Transform::Id),
             "R90" => Ok(Transform::Rotate90),
             "R180" => Ok(Transform::Rotate180),
             "R270" => Ok(Transform::Rotate270),
@@ -123,6 +143,57 @@ impl Transform {
                 | Transform::FlipAntidiag
         )
     }
+
+    /// The image of a cell under this transformation, in a world
+    /// of the given size.
+    ///
+    /// This is how a cell moves when the pattern returns to the first
+    /// generation after a period, before the translation by `dx` and `dy`.
+    /// The generation is unchanged.
+    pub fn apply(self, coord: Coord, width: isize, height: isize) -> Coord {
+        let (x, y, t) = coord;
+        let (x, y) = match self {
+            Transform::Id => (x, y),
+            Transform::Rotate90 => (height - 1 - y, x),
+            Transform::Rotate180 => (width - 1 - x, height - 1 - y),
+            Transform::Rotate270 => (y, width - 1 - x),
+            Transform::FlipRow => (x, height - 1 - y),
+            Transform::FlipCol => (width - 1 - x, y),
+            Transform::FlipDiag => (y, x),
+            Transform::FlipAntidiag => (height - 1 - y, width - 1 - x),
+        };
+        (x, y, t)
+    }
+
+    /// The inverse transformation.
+    ///
+    /// Rotations by 90° and 270° are the inverses of each other;
+    /// every other transformation is its own inverse.
+    pub fn inverse(self) -> Self {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            _ => self,
+        }
+    }
+}
+
+/// Composition of transformations.
+///
+/// `a * b` is the transformation that applies `a` first, then `b`.
+impl Mul for Transform {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // In a 3×3 world, a transformation is determined by the images
+        // of a corner and the middle of an adjacent edge.
+        let image = |f: &dyn Fn(Coord) -> Coord| [f((0, 0, 0)), f((1, 0, 0))];
+        let product = image(&|c| rhs.apply(self.apply(c, 3, 3), 3, 3));
+        *TRANSFORMS
+            .iter()
+            .find(|t| image(&|c| t.apply(c, 3, 3)) == product)
+            .unwrap()
+    }
 }
 
 /// Symmetries of the pattern.
@@ -135,7 +206,18 @@ impl Transform {
 /// Please see the [Life Wiki](https://conwaylife.com/wiki/Symmetry) for details.
 ///
 /// Some of the symmetries are only valid when the world is square.
-#[derive(Clone, Copy, Derivative, PartialEq, Eq)]
+///
+/// The symmetries are centered at the center of the world.
+/// On a torus, they can be combined with a translation by
+/// `symmetry_offset` of `Config`, which gives off-center symmetries,
+/// and glide symmetries (a reflection combined with a translation
+/// along its axis), e.g., for agars.
+///
+/// With the default `Boundary::Dead`, cells outside of the world are dead,
+/// so an off-center symmetry would be the same as a symmetry of a smaller
+/// world, and a glide symmetry would force every cell to be dead.
+/// Thus `symmetry_offset` must be zero there.
+#[derive(Clone, Copy, Derivative, PartialEq, Eq, Hash)]
 #[derivative(Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Symmetry {
@@ -184,11 +266,15 @@ pub enum Symmetry {
     D8,
 }
 
+/// Parses a symmetry.
+///
+/// Surrounding whitespaces are ignored, and lowercase letters are accepted.
+/// `D4x` is also accepted as `D4X`.
 impl FromStr for Symmetry {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+        match s.trim().to_uppercase().as_str() {
             "C1" => Ok(Symmetry::C1),
             "C2" => Ok(Symmetry::C2),
             "C4" => Ok(Symmetry::C4),
@@ -237,13 +323,58 @@ impl Symmetry {
                 | Symmetry::D8
         )
     }
+
+    /// The transformations in this symmetry group, except `Id`.
+    fn transforms(self) -> &'static [Transform] {
+        match self {
+            Symmetry::C1 => &[],
+            Symmetry::C2 => &[Transform::Rotate180],
+            Symmetry::C4 => &[
+                Transform::Rotate270,
+                Transform::Rotate180,
+                Transform::Rotate90,
+            ],
+            Symmetry::D2Row => &[Transform::FlipRow],
+            Symmetry::D2Col => &[Transform::FlipCol],
+            Symmetry::D2Diag => &[Transform::FlipDiag],
+            Symmetry::D2Antidiag => &[Transform::FlipAntidiag],
+            Symmetry::D4Ortho => &[Transform::FlipCol, Transform::FlipRow, Transform::Rotate180],
+            Symmetry::D4Diag => &[
+                Transform::FlipDiag,
+                Transform::FlipAntidiag,
+                Transform::Rotate180,
+            ],
+            Symmetry::D8 => &[
+                Transform::Rotate270,
+                Transform::Rotate90,
+                Transform::FlipCol,
+                Transform::FlipRow,
+                Transform::FlipDiag,
+                Transform::FlipAntidiag,
+                Transform::Rotate180,
+            ],
+        }
+    }
+
+    /// The images of a cell under the transformations in this symmetry
+    /// group, in a world of the given size.
+    ///
+    /// The identity is skipped, so the cell itself is not included
+    /// unless some other transformation fixes it. The generation is
+    /// unchanged.
+    pub fn coords(self, coord: Coord, width: isize, height: isize) -> Vec<Coord> {
+        self.transforms()
+            .iter()
+            .map(|transform| transform.apply(coord, width, height))
+            .collect()
+    }
 }
 
 /// The order to find a new unknown cell.
 ///
 /// It will always search all generations of one cell
 /// before going to another cell.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum SearchOrder {
     /// Searches all cells of one row before going to the next row.
@@ -276,8 +407,98 @@ pub enum SearchOrder {
     Diagonal,
 }
 
+/// The corner of the world where the search starts.
+///
+/// The cells are searched in the order given by `SearchOrder`,
+/// reflected so that the first cell is at this corner.
+/// For example, `RowFirst` starting from `BottomRight`:
+///
+/// ```plaintext
+/// 987
+/// 654
+/// 321
+/// ```
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq, Hash)]
+#[derivative(Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Corner {
+    /// Starts from the cell `(0, 0)`.
+    #[derivative(Default)]
+    TopLeft,
+
+    /// Starts from the cell `(width - 1, 0)`.
+    TopRight,
+
+    /// Starts from the cell `(0, height - 1)`.
+    BottomLeft,
+
+    /// Starts from the cell `(width - 1, height - 1)`.
+    BottomRight,
+}
+
+/// How living cells are counted for `max_cell_count`
+/// and `max_cell_count_per_gen`.
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq, Hash)]
+#[derivative(Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum CountMode {
+    /// Counts all living cells.
+    #[derivative(Default)]
+    All,
+
+    /// Counts one living cell for each orbit under the `symmetry`,
+    /// i.e., each set of cells that are symmetric to each other.
+    ///
+    /// For example, with `D8` symmetry, a limit of `1` allows the 8
+    /// cells of a generic orbit, or the 4 cells of an orbit on an axis.
+    OrbitRepresentatives,
+}
+
+/// What lies beyond the edges of the world.
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq, Hash)]
+#[derivative(Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Boundary {
+    /// All cells outside the world are in the background state.
+    ///
+    /// For rules without `B0`, they are dead.
+    #[derivative(Default)]
+    Dead,
+
+    /// The world wraps around, so that the left edge is adjacent to
+    /// the right edge, and the top edge is adjacent to the bottom edge.
+    ///
+    /// The patterns found tile the plane periodically.
+    /// This requires the translation to be zero.
+    Torus,
+}
+
+/// What counts as a trivial result, which is skipped by the search.
+///
+/// The default rejects empty patterns and patterns whose period is
+/// smaller than the given period.
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq, Hash)]
+#[derivative(Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Triviality {
+    /// Rejects patterns with fewer living cells than this number
+    /// in generation 0.
+    ///
+    /// `0` accepts empty patterns.
+    #[derivative(Default(value = "1"))]
+    pub min_cell_count: usize,
+
+    /// Whether to reject patterns whose actual period is smaller than
+    /// the given period.
+    ///
+    /// See `strict_period` of `Config` for how the actual period
+    /// is determined.
+    #[derivative(Default(value = "true"))]
+    pub reject_subperiodic: bool,
+}
+
 /// How to choose a state for an unknown cell.
-#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Derivative)]
 #[derivative(Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum NewState {
@@ -310,12 +531,62 @@ pub enum NewState {
     /// For Generations rules with `n` states,
     /// the probability of each state is `1/n`.
     Random,
+
+    /// Random, choosing the opposite of the background state
+    /// with the given probability, and the background state otherwise,
+    /// like `ChooseAlive` and `ChooseDead`.
+    ///
+    /// The probability must be between `0` and `1`.
+    /// A small probability often finds sparse patterns faster.
+    ///
+    /// Dying states of Generations rules are never chosen.
+    RandomBiased(f64),
+}
+
+/// Probabilities of `RandomBiased` are compared by their bits,
+/// so that `NewState` can be `Eq` and `Hash`.
+impl PartialEq for NewState {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (NewState::RandomBiased(p), NewState::RandomBiased(q)) => p.to_bits() == q.to_bits(),
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for NewState {}
+
+impl Hash for NewState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        if let NewState::RandomBiased(p) = self {
+            p.to_bits().hash(state);
+        }
+    }
+}
+
+/// How to choose the next unknown cell to decide.
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq, Hash)]
+#[derivative(Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum DecisionHeuristic {
+    /// Chooses the first unknown cell in the search order.
+    #[derivative(Default)]
+    Positional,
+
+    /// Chooses the unknown cell with the highest activity.
+    ///
+    /// The activity of a cell is increased whenever the cell is involved
+    /// in a conflict, and decays as more conflicts occur, as in the
+    /// VSIDS heuristic of SAT solvers.
+    /// Ties are broken by the search order.
+    Vsids,
 }
 
 /// World configuration.
 ///
 /// The world will be generated from this configuration.
-#[derive(Clone, Debug, Derivative, PartialEq, Eq)]
+#[derive(Clone, Debug, Derivative, PartialEq, Eq, Hash)]
 #[derivative(Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Config {
@@ -332,6 +603,10 @@ pub struct Config {
     pub period: isize,
 
     /// Horizontal translation.
+    ///
+    /// `dx` and `dy` are independent, so oblique spaceships can be
+    /// searched too, e.g., `(2,1)c/6` knightships with `dx = 2`,
+    /// `dy = 1` and `period = 6`.
     pub dx: isize,
 
     /// Vertical translation.
@@ -348,43 +623,255 @@ pub struct Config {
     /// Symmetries of the pattern.
     pub symmetry: Symmetry,
 
+    /// A translation applied after each transformation of the symmetry,
+    /// wrapping around the edges of the world.
+    ///
+    /// For example, `D2-` with an offset of half the width is a glide
+    /// symmetry. This requires `Boundary::Torus` and a symmetry other
+    /// than `C1`. See `Symmetry` for details.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub symmetry_offset: (isize, isize),
+
+    /// What lies beyond the edges of the world.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub boundary: Boundary,
+
     /// The order to find a new unknown cell.
     ///
     /// It will always search all generations of one cell
     /// before going to another cell.
     ///
     /// `None` means that it will automatically choose a search order
-    /// according to the width and height of the world.
+    /// according to the width and height of the world, and the
+    /// translation of the pattern.
     pub search_order: Option<SearchOrder>,
 
+    /// The corner of the world where the search starts.
+    ///
+    /// Note that `non_empty_front` still refers to the first row or
+    /// column of the world, whichever corner the search starts from.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub start_corner: Corner,
+
     /// How to choose a state for an unknown cell.
     pub new_state: NewState,
 
+    /// The seed of the random number generator used by
+    /// `NewState::Random` and `NewState::RandomBiased`.
+    ///
+    /// `None` means that the generator is seeded from the system entropy,
+    /// so that the search is not reproducible.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub seed: Option<u64>,
+
+    /// How to choose the next unknown cell to decide.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub decision_heuristic: DecisionHeuristic,
+
+    /// Whether to reuse the last state of a cell when deciding it again.
+    ///
+    /// When the backtracking clears a cell, its state is saved.
+    /// With this option, the saved state is chosen when the cell is
+    /// decided again, instead of the state given by `new_state`.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub phase_saving: bool,
+
     /// The number of minimum living cells in all generations must not
     /// exceed this number.
     ///
+    /// See `count_mode` for counting only one cell in each orbit
+    /// under the symmetry.
+    ///
     /// `None` means that there is no limit for the cell count.
     pub max_cell_count: Option<usize>,
 
+    /// The number of living cells in each generation must not
+    /// exceed this number.
+    ///
+    /// Unlike `max_cell_count`, which only bounds the generation with
+    /// the fewest living cells, this bounds every generation.
+    ///
+    /// `None` means that there is no limit for the cell count.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub max_cell_count_per_gen: Option<usize>,
+
+    /// How living cells are counted for `max_cell_count`
+    /// and `max_cell_count_per_gen`.
+    ///
+    /// This does not change the cell count reported by the search.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub count_mode: CountMode,
+
+    /// Maximal number of cells in the world, i.e., `width * height * period`.
+    ///
+    /// Larger worlds are rejected with `Error::WorldSizeError` when the world
+    /// is created, instead of trying to allocate them. Worlds that are too
+    /// large to be addressed are always rejected.
+    ///
+    /// `None` means that there is no such limit.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub max_cells: Option<usize>,
+
     /// Whether to force the first row/column to be nonempty.
     ///
     /// Here 'front' means the first row or column to be searched,
-    /// according to the search order.
+    /// according to the search order. See `front_lines` for using
+    /// several rows or columns as the front.
     #[derivative(Default(value = "true"))]
     pub non_empty_front: bool,
 
+    /// Number of rows or columns in the front.
+    ///
+    /// When `non_empty_front` is set, these rows or columns together
+    /// must contain a living cell. `1` means only the first row or column.
+    #[derivative(Default(value = "1"))]
+    #[cfg_attr(feature = "serialize", serde(default = "default_front_lines"))]
+    pub front_lines: usize,
+
+    /// Whether to force generation 0 to be nonempty.
+    ///
+    /// Unlike `non_empty_front`, this does not depend on the search order,
+    /// and the search backtracks as soon as every cell in generation 0
+    /// is known to be dead.
+    ///
+    /// Empty results are already skipped at the end when the
+    /// `min_cell_count` in `triviality` is positive. This option prunes
+    /// them earlier, and also forbids them when `min_cell_count` is `0`.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub forbid_empty: bool,
+
     /// Whether to automatically reduce the `max_cell_count`
     /// when a result is found.
     ///
     /// The `max_cell_count` will be set to the cell count of
-    /// the current result minus one.
+    /// the current result minus one, so that each subsequent result
+    /// is strictly smaller than the previous one.
     pub reduce_max: bool,
 
+    /// Whether to reject patterns whose actual period is smaller than
+    /// the given period.
+    ///
+    /// Without this option, only patterns that return to themselves
+    /// in place after fewer generations are rejected. With this option,
+    /// rotations, reflections and translations are also taken into
+    /// account. For example, a c/2 spaceship is rejected when searching
+    /// for 2c/4 spaceships.
+    ///
+    /// This has no effect if `reject_subperiodic` in `triviality`
+    /// is `false`.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub strict_period: bool,
+
+    /// What counts as a trivial result, which is skipped by the search.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub triviality: Triviality,
+
+    /// A rectangle `(x, y, width, height)` in the world that contains
+    /// all living cells of generation 0.
+    ///
+    /// Cells of generation 0 outside this rectangle are dead.
+    /// Unlike a smaller world, the cells between the rectangle and
+    /// the edges of the world are still part of the search, so other
+    /// generations may extend beyond the rectangle.
+    ///
+    /// Note that `non_empty_front` still refers to the first row or
+    /// column of the world, not of the rectangle. It should usually be
+    /// disabled if the rectangle does not contain the first row or column.
+    ///
+    /// `None` means that there is no such rectangle.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub bounding_box: Option<(isize, isize, isize, isize)>,
+
+    /// Cells whose states are known before the search.
+    ///
+    /// The coordinates are the same as in `get_cell_state`.
+    /// The search will never change the states of these cells.
+    ///
+    /// The known cells need not be symmetric: the cells that are
+    /// symmetric to a known cell under `symmetry` get the same state
+    /// automatically. But if two known cells are symmetric to each other
+    /// and have different states, the world cannot be created, and
+    /// `Error::SymmetryKnownCellsError` is returned.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub known_cells: Vec<(Coord, State)>,
+
+    /// A pattern that must not appear in the result.
+    ///
+    /// The result is rejected if all these cells have the given states
+    /// at the same time. The coordinates are the same as in
+    /// `get_cell_state`.
+    ///
+    /// `Vec::new()` means that nothing is forbidden.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub forbidden: Vec<(Coord, State)>,
+
+    /// Cells in the stator, i.e., cells that have the same state
+    /// in every generation.
+    ///
+    /// Only the `x` and `y` coordinates are given.
+    /// The cells must lie within the world.
+    ///
+    /// `Vec::new()` means that every cell may be in the rotor.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub stator: Vec<(isize, isize)>,
+
+    /// Whether to restart the search from time to time.
+    ///
+    /// The search backtracks to the first decision whenever the number
+    /// of conflicts since the last restart reaches a threshold given by
+    /// the [Luby sequence](https://doi.org/10.1016/0020-0190(93)90029-9).
+    /// Cells that are already determined before the first decision
+    /// are kept.
+    ///
+    /// Restarts are most useful when `new_state` is `NewState::Random`.
+    /// They stop after the first result is found, so that the same
+    /// result is not found twice.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub restart: bool,
+
+    /// The maximal decision level, i.e., the number of cells decided
+    /// by choice at the same time.
+    ///
+    /// When a new decision would exceed this level, it is treated as a
+    /// conflict, and the search backtracks.
+    ///
+    /// This is an advanced option to keep the search from going too deep.
+    /// A level that is too small can make the search miss results,
+    /// so that it returns `Status::None` even if a result exists.
+    ///
+    /// `None` means that there is no limit.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub max_decision_level: Option<usize>,
+
     /// The rule string of the cellular automaton.
+    ///
+    /// Besides totalistic and isotropic non-totalistic rules,
+    /// non-isotropic rules in Golly's `MAP` format are also supported.
+    /// They are handled by the same rule type as isotropic non-totalistic
+    /// rules.
+    ///
+    /// Hexagonal rules, whose rule strings end with `H`, are emulated
+    /// on the Moore neighborhood as in Golly: the top-right and
+    /// bottom-left neighbors are ignored.
+    /// Similarly, rules with von Neumann neighborhoods, whose rule strings
+    /// end with `V`, ignore the four diagonal neighbors.
+    ///
+    /// Two non-Generations rules without `B0` separated by `|`, e.g.,
+    /// `B3/S23|B36/S23`, form a checkerboard rule: cells with even `x + y`
+    /// follow the first rule, and cells with odd `x + y` follow the second.
+    /// The translation, the transformation, the symmetry and the boundary
+    /// must preserve the two colors, e.g., `dx + dy` must be even for
+    /// a translation without a transformation.
     #[derivative(Default(value = "String::from(\"B3/S23\")"))]
     pub rule_string: String,
 }
 
+/// Default value of `front_lines` for deserialization.
+#[cfg(feature = "serialize")]
+fn default_front_lines() -> usize {
+    1
+}
+
 impl Config {
     /// Sets up a new configuration with given size.
     pub fn new(width: isize, height: isize, period: isize) -> Self {
@@ -396,6 +883,119 @@ impl Config {
         }
     }
 
+    /// Names of the preset configurations. See `preset`.
+    pub const PRESETS: [&'static str; 7] = [
+        "c/2 orthogonal spaceship",
+        "c/3 orthogonal spaceship",
+        "c/3 orthogonal spaceship with symmetry",
+        "c/4 diagonal spaceship",
+        "p2 oscillator",
+        "p3 oscillator",
+        "16x16 still life",
+    ];
+
+    /// A preset configuration for Conway's Game of Life,
+    /// which finds a result in a few seconds.
+    ///
+    /// Returns `None` if the name is not in `PRESETS`.
+    pub fn preset(name: &str) -> Option<Self> {
+        let config = match name {
+            "c/2 orthogonal spaceship" => Config::new(8, 16, 4).set_translate(0, 2),
+            "c/3 orthogonal spaceship" => Config::new(16, 5, 3).set_translate(0, 1),
+            "c/3 orthogonal spaceship with symmetry" => Config::new(12, 13, 3)
+                .set_translate(0, 1)
+                .set_symmetry(Symmetry::D2Col),
+            "c/4 diagonal spaceship" => Config::new(6, 6, 4).set_translate(1, 1),
+            "p2 oscillator" => Config::new(6, 6, 2).set_strict_period(true),
+            "p3 oscillator" => Config::new(13, 13, 3)
+                .set_symmetry(Symmetry::D8)
+                .set_strict_period(true),
+            "16x16 still life" => Config::new(16, 16, 1),
+            _ => return None,
+        };
+        Some(config)
+    }
+
+    /// Sets up a new configuration from a pattern in
+    /// [RLE](https://conwaylife.com/wiki/Rle) format.
+    ///
+    /// The width, height and rule string are read from the header line.
+    /// Living and dying cells in the pattern are added to `known_cells`
+    /// in generation 0. Dead cells, cells marked by `?`, and cells not
+    /// specified in the pattern are left unknown, so that the search can
+    /// complete the pattern.
+    ///
+    /// The period is 1. Other parameters can be set afterwards.
+    ///
+    /// Runs that go beyond the width or height in the header are rejected.
+    pub fn from_rle(rle: &str) -> Result<Self, String> {
+        let mut lines = rle
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let header = lines.next().ok_or("missing RLE header")?;
+        let mut config = Config::default();
+        let (mut width, mut height) = (None, None);
+        for item in header.split(',') {
+            let mut kv = item.splitn(2, '=').map(str::trim);
+            let key = kv.next().unwrap();
+            let value = kv.next().ok_or("invalid RLE header")?;
+            match key {
+                "x" => width = Some(value.parse().map_err(|_| "invalid width")?),
+                "y" => height = Some(value.parse().map_err(|_| "invalid height")?),
+                "rule" => config.rule_string = value.to_string(),
+                _ => (),
+            }
+        }
+        config.width = width.ok_or("missing width in RLE header")?;
+        config.height = height.ok_or("missing height in RLE header")?;
+
+        let (mut x, mut y) = (0, 0);
+        let mut count: Option<isize> = None;
+        'outer: for line in lines {
+            for c in line.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    count = count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|count| count.checked_add(digit as isize));
+                    if count.is_none() {
+                        return Err("run count too large in RLE".to_string());
+                    }
+                    continue;
+                } else if c.is_whitespace() {
+                    continue;
+                }
+                let n = count.take().unwrap_or(1);
+                let state = match c {
+                    'b' | '.' | '?' => None,
+                    'o' => Some(State(1)),
+                    'A'..='Z' => Some(State((c as u8 - b'A') as usize + 1)),
+                    '$' => {
+                        if n > config.height - y {
+                            return Err("pattern is taller than the RLE header".to_string());
+                        }
+                        x = 0;
+                        y += n;
+                        continue;
+                    }
+                    '!' => break 'outer,
+                    _ => return Err(format!("unexpected {:?} in RLE", c)),
+                };
+                if y >= config.height || n > config.width - x {
+                    return Err("pattern is wider than the RLE header".to_string());
+                }
+                if let Some(state) = state {
+                    for i in 0..n {
+                        config.known_cells.push(((x + i, y, 0), state));
+                    }
+                }
+                x += n;
+            }
+        }
+        Ok(config)
+    }
+
     /// Sets the translations `(dx, dy)`.
     pub fn set_translate(mut self, dx: isize, dy: isize) -> Self {
         self.dx = dx;
@@ -415,30 +1015,96 @@ impl Config {
         self
     }
 
+    /// Sets the translation applied after each transformation of the symmetry.
+    pub fn set_symmetry_offset(mut self, dx: isize, dy: isize) -> Self {
+        self.symmetry_offset = (dx, dy);
+        self
+    }
+
+    /// Sets what lies beyond the edges of the world.
+    pub fn set_boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
     /// Sets the search order.
     pub fn set_search_order(mut self, search_order: Option<SearchOrder>) -> Self {
         self.search_order = search_order;
         self
     }
 
+    /// Sets the corner of the world where the search starts.
+    pub fn set_start_corner(mut self, start_corner: Corner) -> Self {
+        self.start_corner = start_corner;
+        self
+    }
+
     /// Sets how to choose a state for an unknown cell.
     pub fn set_new_state(mut self, new_state: NewState) -> Self {
         self.new_state = new_state;
         self
     }
 
+    /// Sets the seed of the random number generator.
+    pub fn set_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets how to choose the next unknown cell to decide.
+    pub fn set_decision_heuristic(mut self, decision_heuristic: DecisionHeuristic) -> Self {
+        self.decision_heuristic = decision_heuristic;
+        self
+    }
+
+    /// Sets whether to reuse the last state of a cell when deciding it again.
+    pub fn set_phase_saving(mut self, phase_saving: bool) -> Self {
+        self.phase_saving = phase_saving;
+        self
+    }
+
     /// Sets the maximal number of living cells.
     pub fn set_max_cell_count(mut self, max_cell_count: Option<usize>) -> Self {
         self.max_cell_count = max_cell_count;
         self
     }
 
+    /// Sets the maximal number of living cells in each generation.
+    pub fn set_max_cell_count_per_gen(mut self, max_cell_count_per_gen: Option<usize>) -> Self {
+        self.max_cell_count_per_gen = max_cell_count_per_gen;
+        self
+    }
+
+    /// Sets how living cells are counted for the max cell counts.
+    pub fn set_count_mode(mut self, count_mode: CountMode) -> Self {
+        self.count_mode = count_mode;
+        self
+    }
+
+    /// Sets the maximal number of cells in the world.
+    pub fn set_max_cells(mut self, max_cells: Option<usize>) -> Self {
+        self.max_cells = max_cells;
+        self
+    }
+
     /// Sets whether to force the first row/column to be nonempty.
     pub fn set_non_empty_front(mut self, non_empty_front: bool) -> Self {
         self.non_empty_front = non_empty_front;
         self
     }
 
+    /// Sets whether to force generation 0 to be nonempty.
+    pub fn set_forbid_empty(mut self, forbid_empty: bool) -> Self {
+        self.forbid_empty = forbid_empty;
+        self
+    }
+
+    /// Sets the number of rows or columns in the front.
+    pub fn set_front_lines(mut self, front_lines: usize) -> Self {
+        self.front_lines = front_lines;
+        self
+    }
+
     /// Sets whether to automatically reduce the `max_cell_count`
     /// when a result is found.
     pub fn set_reduce_max(mut self, reduce_max: bool) -> Self {
@@ -446,17 +1112,75 @@ impl Config {
         self
     }
 
+    /// Sets whether to reject patterns whose actual period is smaller
+    /// than the given period.
+    pub fn set_strict_period(mut self, strict_period: bool) -> Self {
+        self.strict_period = strict_period;
+        self
+    }
+
+    /// Sets what counts as a trivial result.
+    pub fn set_triviality(mut self, triviality: Triviality) -> Self {
+        self.triviality = triviality;
+        self
+    }
+
+    /// Sets the rectangle that contains all living cells of generation 0.
+    pub fn set_bounding_box(mut self, bounding_box: Option<(isize, isize, isize, isize)>) -> Self {
+        self.bounding_box = bounding_box;
+        self
+    }
+
+    /// Sets the cells whose states are known before the search.
+    pub fn set_known_cells(mut self, known_cells: Vec<(Coord, State)>) -> Self {
+        self.known_cells = known_cells;
+        self
+    }
+
+    /// Sets the pattern that must not appear in the result.
+    pub fn set_forbidden(mut self, forbidden: Vec<(Coord, State)>) -> Self {
+        self.forbidden = forbidden;
+        self
+    }
+
+    /// Sets the cells in the stator.
+    pub fn set_stator(mut self, stator: Vec<(isize, isize)>) -> Self {
+        self.stator = stator;
+        self
+    }
+
+    /// Sets whether to restart the search from time to time.
+    pub fn set_restart(mut self, restart: bool) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Sets the maximal decision level.
+    pub fn set_max_decision_level(mut self, max_decision_level: Option<usize>) -> Self {
+        self.max_decision_level = max_decision_level;
+        self
+    }
+
     /// Sets the rule string.
     pub fn set_rule_string<S: ToString>(mut self, rule_string: S) -> Self {
         self.rule_string = rule_string.to_string();
         self
     }
 
-    /// Automatically determines the search order if `search_order` is `None`.
+    /// The search order that is actually used by the search.
+    ///
+    /// It is `search_order` if it is not `None`; otherwise it is
+    /// automatically determined from the size, the symmetry and
+    /// the translation of the world.
     ///
-    /// The result will never be `SearchOrder::Diagonal`.
-    pub(crate) fn auto_search_order(&self) -> SearchOrder {
+    /// The result is `SearchOrder::Diagonal` only if the world is square
+    /// and the pattern moves diagonally, i.e., `dx` and `dy` have the same
+    /// nonzero absolute value.
+    pub fn auto_search_order(&self) -> SearchOrder {
         self.search_order.unwrap_or_else(|| {
+            if self.width == self.height && self.dx != 0 && self.dx.abs() == self.dy.abs() {
+                return SearchOrder::Diagonal;
+            }
             let (width, height) = match self.symmetry {
                 Symmetry::D2Row => (self.width, (self.height + 1) / 2),
                 Symmetry::D2Col => ((self.width + 1) / 2, self.height),
@@ -481,16 +1205,7 @@ impl Config {
         let (mut x, mut y, mut t) = coord;
         while t < 0 {
             t += self.period;
-            let (new_x, new_y) = match self.transform {
-                Transform::Id => (x, y),
-                Transform::Rotate90 => (self.height - 1 - y, x),
-                Transform::Rotate180 => (self.width - 1 - x, self.height - 1 - y),
-                Transform::Rotate270 => (y, self.width - 1 - x),
-                Transform::FlipRow => (x, self.height - 1 - y),
-                Transform::FlipCol => (self.width - 1 - x, y),
-                Transform::FlipDiag => (y, x),
-                Transform::FlipAntidiag => (self.height - 1 - y, self.width - 1 - x),
-            };
+            let (new_x, new_y, _) = self.transform.apply((x, y, t), self.width, self.height);
             x = new_x - self.dx;
             y = new_y - self.dy;
         }
@@ -498,51 +1213,317 @@ impl Config {
             t -= self.period;
             x += self.dx;
             y += self.dy;
-            let (new_x, new_y) = match self.transform {
-                Transform::Id => (x, y),
-                Transform::Rotate90 => (y, self.width - 1 - x),
-                Transform::Rotate180 => (self.width - 1 - x, self.height - 1 - y),
-                Transform::Rotate270 => (self.height - 1 - y, x),
-                Transform::FlipRow => (x, self.height - 1 - y),
-                Transform::FlipCol => (self.width - 1 - x, y),
-                Transform::FlipDiag => (y, x),
-                Transform::FlipAntidiag => (self.height - 1 - y, self.width - 1 - x),
-            };
+            let (new_x, new_y, _) =
+                self.transform
+                    .inverse()
+                    .apply((x, y, t), self.width, self.height);
             x = new_x;
             y = new_y;
         }
         (x, y, t)
     }
 
-    /// Creates a new world from the configuration.
-    /// Returns an error if the rule string is invalid.
-    pub fn world(&self) -> Result<Box<dyn Search>, Error> {
-        if (self.symmetry.square_world()
-            || self.transform.square_world()
-            || self.search_order == Some(SearchOrder::Diagonal))
-            && self.width != self.height
-        {
-            return Err(Error::SquareWorldError);
-        }
-        if let Ok(rule) = self.rule_string.parse::<Life>() {
-            Ok(Box::new(World::new(&self, rule)))
-        } else if let Ok(rule) = self.rule_string.parse::<NtLife>() {
-            Ok(Box::new(World::new(&self, rule)))
-        } else if let Ok(rule) = self.rule_string.parse::<LifeGen>() {
-            if rule.gen() > 2 {
-                Ok(Box::new(World::new(&self, rule)))
-            } else {
-                let rule = rule.non_gen();
-                Ok(Box::new(World::new(&self, rule)))
+    /// Reflects a coord so that `start_corner` becomes the top left corner.
+    ///
+    /// The reflection is its own inverse. The search order is applied to
+    /// the reflected coords, so that the search starts from the corner.
+    pub(crate) fn flip_to_start_corner(&self, coord: Coord) -> Coord {
+        let (x, y, t) = coord;
+        let (flip_x, flip_y) = match self.start_corner {
+            Corner::TopLeft => (false, false),
+            Corner::TopRight => (true, false),
+            Corner::BottomLeft => (false, true),
+            Corner::BottomRight => (true, true),
+        };
+        let x = if flip_x { self.width - 1 - x } else { x };
+        let y = if flip_y { self.height - 1 - y } else { y };
+        (x, y, t)
+    }
+
+    /// The images of a cell under the transformations of the symmetry,
+    /// except `Id`, followed by the translation by `symmetry_offset`.
+    ///
+    /// The translation wraps around the edges of the world. This is only
+    /// meaningful on a torus, as `validate` ensures.
+    pub(crate) fn symmetry_coords(&self, coord: Coord) -> Vec<Coord> {
+        let mut coords = self.symmetry.coords(coord, self.width, self.height);
+        let (dx, dy) = self.symmetry_offset;
+        if (dx, dy) != (0, 0) {
+            for (x, y, _) in coords.iter_mut() {
+                *x = (*x + dx).rem_euclid(self.width);
+                *y = (*y + dy).rem_euclid(self.height);
             }
+        }
+        coords
+    }
+
+    /// Checks whether the configuration is consistent.
+    ///
+    /// Returns an error naming the inconsistent field if:
+    ///
+    /// * the width, the height, the period or `front_lines` is not positive;
+    /// * the world is not square, but the transformation, the symmetry
+    ///   or the search order requires a square world;
+    /// * the symmetry does not preserve the displacement of the pattern.
+    ///   For example, a pattern with `D2|` symmetry cannot move horizontally.
+    ///   When the transformation is not `Id`, the displacement after
+    ///   applying it several times until it returns to `Id` is considered;
+    /// * `symmetry_offset` is not zero, but the world is not a torus,
+    ///   or the symmetry is `C1`.
+    ///
+    /// Only the first problem is reported. See `build` for all of them.
+    pub fn validate(&self) -> Result<(), Error> {
+        match self.errors().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the configuration and the rule string,
+    /// and returns the configuration if there is no problem.
+    ///
+    /// Unlike `validate`, it collects all the problems instead of
+    /// stopping at the first one. The rule string is checked too.
+    ///
+    /// The setters never fail, so this is meant to be called at the
+    /// end of a chain of setters, before creating a world.
+    pub fn build(self) -> Result<Self, Vec<Error>> {
+        let mut errors = self.errors();
+        if let Err(error) = validate_rule_string(&self.rule_string) {
+            errors.push(error);
+        }
+        if errors.is_empty() {
+            Ok(self)
         } else {
-            let rule = self.rule_string.parse::<NtLifeGen>()?;
-            if rule.gen() > 2 {
-                Ok(Box::new(World::new(&self, rule)))
-            } else {
-                let rule = rule.non_gen();
-                Ok(Box::new(World::new(&self, rule)))
+            Err(errors)
+        }
+    }
+
+    /// All the problems found by `validate`.
+    fn errors(&self) -> Vec<Error> {
+        let mut errors = Vec::new();
+        if self.width <= 0 {
+            errors.push(Error::NonPositiveError("width"));
+        }
+        if self.height <= 0 {
+            errors.push(Error::NonPositiveError("height"));
+        }
+        if self.period <= 0 {
+            errors.push(Error::NonPositiveError("period"));
+        }
+        if self.front_lines == 0 {
+            errors.push(Error::NonPositiveError("front_lines"));
+        }
+        if self.width > 0 && self.height > 0 && self.period > 0 {
+            let size = self
+                .width
+                .checked_mul(self.height)
+                .and_then(|size| size.checked_mul(self.period));
+            let too_large = match (size, self.max_cells) {
+                (None, _) => true,
+                (Some(size), Some(max)) => size as usize > max,
+                (Some(_), None) => false,
+            };
+            if too_large {
+                errors.push(Error::WorldSizeError);
+            }
+        }
+        if self.width != self.height {
+            if self.transform.square_world() {
+                errors.push(Error::SquareWorldError("transform"));
+            }
+            if self.symmetry.square_world() {
+                errors.push(Error::SquareWorldError("symmetry"));
+            }
+            if self.search_order == Some(SearchOrder::Diagonal) {
+                errors.push(Error::SquareWorldError("search_order"));
             }
         }
+
+        if let Some((x, y, width, height)) = self.bounding_box {
+            if width <= 0 || height <= 0 {
+                errors.push(Error::NonPositiveError("bounding_box"));
+            } else if x < 0
+                || y < 0
+                || x.checked_add(width)
+                    .filter(|&right| right <= self.width)
+                    .is_none()
+                || y.checked_add(height)
+                    .filter(|&bottom| bottom <= self.height)
+                    .is_none()
+            {
+                errors.push(Error::BoundingBoxError);
+            }
+        }
+
+        if self.boundary == Boundary::Torus && (self.dx != 0 || self.dy != 0) {
+            errors.push(Error::TorusTranslateError);
+        }
+
+        if self.symmetry_offset != (0, 0)
+            && (self.boundary != Boundary::Torus || self.symmetry == Symmetry::C1)
+        {
+            errors.push(Error::SymmetryOffsetError);
+        }
+
+        if let NewState::RandomBiased(p) = self.new_state {
+            if !(0.0..=1.0).contains(&p) {
+                errors.push(Error::ProbabilityError);
+            }
+        }
+
+        // A displacement that overflows cannot be preserved by any symmetry
+        // other than `C1`.
+        let (dx, dy) = (self.dx, self.dy);
+        let displacement = match self.transform {
+            Transform::Id => Some((dx, dy)),
+            Transform::Rotate90 | Transform::Rotate180 | Transform::Rotate270 => Some((0, 0)),
+            Transform::FlipRow => dx.checked_mul(2).map(|x| (x, 0)),
+            Transform::FlipCol => dy.checked_mul(2).map(|y| (0, y)),
+            Transform::FlipDiag => dx.checked_add(dy).map(|x| (x, x)),
+            Transform::FlipAntidiag => dx
+                .checked_sub(dy)
+                .and_then(|x| dy.checked_sub(dx).map(|y| (x, y))),
+        };
+        let preserved = match (self.symmetry, displacement) {
+            (Symmetry::C1, _) => true,
+            (_, None) => false,
+            (Symmetry::D2Row, Some((_, y))) => y == 0,
+            (Symmetry::D2Col, Some((x, _))) => x == 0,
+            (Symmetry::D2Diag, Some((x, y))) => x == y,
+            (Symmetry::D2Antidiag, Some((x, y))) => Some(x) == y.checked_neg(),
+            (_, Some((x, y))) => x == 0 && y == 0,
+        };
+        if !preserved {
+            errors.push(Error::TranslateSymmetryError);
+        }
+        errors
+    }
+
+    /// Creates a new world from the configuration and a rule that is
+    /// already parsed, e.g., to reuse one rule for many worlds.
+    ///
+    /// The `rule_string` of the configuration is ignored here,
+    /// but it is still used when the world is saved or displayed,
+    /// so it should describe the same rule.
+    ///
+    /// Returns an error if the rule contains both `B0` and `S8`,
+    /// the translation is faster than the speed of light of the rule,
+    /// or the configuration is inconsistent (see `validate`).
+    pub fn world_with_rule<R: Rule>(&self, rule: R) -> Result<Box<dyn Search>, Error> {
+        self.validate()?;
+        if rule.has_b0_s8() {
+            return Err(Error::B0S8Error);
+        }
+        new_world(self, rule)
+    }
+
+    /// Creates a new world from the configuration.
+    /// Returns an error if the rule string is invalid,
+    /// the translation is faster than the speed of light of the rule,
+    /// or the configuration is inconsistent (see `validate`).
+    pub fn world(&self) -> Result<Box<dyn Search>, Error> {
+        self.validate()?;
+        with_rule(&self.rule_string, NewWorld(self))
+    }
+
+    /// Searches in worlds of increasing sizes, e.g., to find the smallest
+    /// spaceship of some velocity.
+    ///
+    /// Starting from the size in the configuration, both the width and
+    /// the height are increased by `step` each time, until one of them
+    /// exceeds `max_dim`. A new world is built for each size, and
+    /// searched with at most `per_size_steps` steps.
+    ///
+    /// Returns the world containing the first result found, if any,
+    /// together with the sizes where the search reached `per_size_steps`
+    /// before finding a result or proving that there is none.
+    /// The size of the result is given by `dimensions` of the world.
+    ///
+    /// A size that timed out is skipped, so when the list is not empty,
+    /// there may be a result that is smaller than the one returned,
+    /// or a result at all when none is returned.
+    pub fn search_increasing(
+        &self,
+        max_dim: isize,
+        step: isize,
+        per_size_steps: Option<u64>,
+    ) -> Result<IncreasingResult, Error> {
+        let mut config = self.clone();
+        let mut timed_out = Vec::new();
+        while config.width <= max_dim && config.height <= max_dim {
+            let mut world = config.world()?;
+            match world.search(per_size_steps) {
+                Status::Found => {
+                    return Ok(IncreasingResult {
+                        world: Some(world),
+                        timed_out,
+                    })
+                }
+                Status::Searching => timed_out.push((config.width, config.height)),
+                _ => (),
+            }
+            config.width += step.max(1);
+            config.height += step.max(1);
+        }
+        Ok(IncreasingResult {
+            world: None,
+            timed_out,
+        })
+    }
+}
+
+/// The result of `Config::search_increasing`.
+pub struct IncreasingResult {
+    /// The world containing the first result found, or `None`.
+    pub world: Option<Box<dyn Search>>,
+    /// The sizes `(width, height)` where the search reached
+    /// the step limit, in the order they were searched.
+    pub timed_out: Vec<(isize, isize)>,
+}
+
+/// Creates a new world from the configuration and the rule,
+/// and sets the known cells.
+fn new_world<R: Rule>(config: &Config, rule: R) -> Result<Box<dyn Search>, Error> {
+    Ok(Box::new(World::new(config, rule)?.init_known()?))
+}
+
+/// Creates a new world from the configuration and a rule of any type.
+/// See `new_world`.
+struct NewWorld<'c>(&'c Config);
+
+impl WithRule for NewWorld<'_> {
+    type Output = Box<dyn Search>;
+
+    fn call<R: Rule>(self, rule: R) -> Result<Self::Output, Error> {
+        new_world(self.0, rule)
+    }
+}
+
+/// Displays a short summary of the configuration,
+/// e.g., `16x5, period 3, translate (0, 1), rule B3/S23`.
+///
+/// The translation, the transformation, the symmetry and the boundary
+/// are omitted if they are trivial.
+impl Display for Config {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}x{}, period {}", self.width, self.height, self.period)?;
+        if self.dx != 0 || self.dy != 0 {
+            write!(f, ", translate ({}, {})", self.dx, self.dy)?;
+        }
+        if self.transform != Transform::Id {
+            write!(f, ", transform {:?}", self.transform)?;
+        }
+        if self.symmetry != Symmetry::C1 {
+            write!(f, ", symmetry {:?}", self.symmetry)?;
+        }
+        if self.symmetry_offset != (0, 0) {
+            let (dx, dy) = self.symmetry_offset;
+            write!(f, ", symmetry offset ({}, {})", dx, dy)?;
+        }
+        if self.boundary == Boundary::Torus {
+            write!(f, ", torus")?;
+        }
+        write!(f, ", rule {}", self.rule_string)
     }
 }
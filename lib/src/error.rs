@@ -9,10 +9,49 @@ pub enum Error {
     GetCellError(Coord),
     #[error("Unable to set cell at {0:?}")]
     SetCellError(Coord),
-    #[error("Invalid rule: {0:?}")]
+    #[error("Invalid rule: {0}")]
     ParseRuleError(#[from] ParseRuleError),
+    /// The position is the index of the character in the rule string
+    /// where parsing fails. For HROT rules, it is where the comma-separated
+    /// item with the error starts.
+    #[error("Invalid rule at position {0}: {1}")]
+    RuleSyntaxError(usize, ParseRuleError),
     #[error("B0S8 rules are not supported yet. Please use the inverted rule.")]
     B0S8Error,
-    #[error("Symmetry or transformation requires the world to be square")]
-    SquareWorldError,
+    #[error("Unsupported HROT rule: {0}")]
+    HrotError(&'static str),
+    #[error("Checkerboard rules with B0 are not supported yet")]
+    CheckerboardB0Error,
+    #[error("A checkerboard rule can only contain two rules")]
+    CheckerboardNestedError,
+    #[error("The translation, the transformation, the symmetry and the boundary must preserve the colors of a checkerboard rule")]
+    CheckerboardColorError,
+    #[error("`{0}` requires the world to be square")]
+    SquareWorldError(&'static str),
+    #[error("`{0}` must be positive")]
+    NonPositiveError(&'static str),
+    #[error("The translation `dx`, `dy` is not preserved by the `symmetry`")]
+    TranslateSymmetryError,
+    #[error("The translation `dx`, `dy` must be zero when the world is a torus")]
+    TorusTranslateError,
+    #[error("The symmetry offset requires a torus and a symmetry other than `C1`")]
+    SymmetryOffsetError,
+    #[error("The translation `dx`, `dy` is faster than the speed of light of the rule")]
+    SpeedOfLightError,
+    #[error("The known cells are inconsistent with the rule or the configuration")]
+    KnownCellsError,
+    #[error("The known cells {0:?} and {1:?} are symmetric but have different states")]
+    SymmetryKnownCellsError(Coord, Coord),
+    #[error("The bounding box does not lie within the world")]
+    BoundingBoxError,
+    #[error("The world is too large")]
+    WorldSizeError,
+    #[error("The probability in `new_state` must be between 0 and 1")]
+    ProbabilityError,
+    #[error(
+        "The saved file has version {0}, which is newer than this version of rlifesrc supports"
+    )]
+    IncompatibleVersion(u32),
+    #[error("Broken compressed saved file: {0}")]
+    CompressedSaveError(String),
 }
@@ -0,0 +1,121 @@
+//! A priority queue of cells for `DecisionHeuristic::Vsids`.
+
+use crate::{cells::CellRef, rules::Rule};
+
+/// A binary max-heap of positions in the `search_list` of a world,
+/// ordered by the activities of the cells.
+///
+/// Ties are broken by the order in the `search_list`.
+///
+/// The heap remembers where each position is stored, so that the activity
+/// of a cell can be increased in place. Cells are not removed when they
+/// are set: known cells at the top are dropped by `peek_unknown`,
+/// and a cell is inserted again when it is cleared.
+#[derive(Default)]
+pub(crate) struct ActivityHeap {
+    /// Positions in the `search_list`.
+    heap: Vec<usize>,
+
+    /// The index in `heap` of each position in the `search_list`,
+    /// or `None` if the position is not in the heap.
+    indices: Vec<Option<usize>>,
+}
+
+impl ActivityHeap {
+    /// Creates a heap containing all the positions in the `search_list`.
+    pub(crate) fn new<R: Rule>(search_list: &[CellRef<R>]) -> Self {
+        let mut heap = ActivityHeap::default();
+        heap.rebuild(search_list);
+        heap
+    }
+
+    /// Puts all the positions in the `search_list` back into the heap,
+    /// and restores the order after the activities have been changed
+    /// from outside, e.g., by `reset`.
+    pub(crate) fn rebuild<R: Rule>(&mut self, search_list: &[CellRef<R>]) {
+        let len = search_list.len();
+        self.heap = (0..len).collect();
+        self.indices = (0..len).map(Some).collect();
+        for i in (0..len / 2).rev() {
+            self.sift_down(i, search_list);
+        }
+    }
+
+    /// Inserts a position, if it is not in the heap.
+    pub(crate) fn insert<R: Rule>(&mut self, pos: usize, search_list: &[CellRef<R>]) {
+        if self.indices[pos].is_none() {
+            self.heap.push(pos);
+            self.indices[pos] = Some(self.heap.len() - 1);
+            self.sift_up(self.heap.len() - 1, search_list);
+        }
+    }
+
+    /// Moves a position up after the activity of its cell is increased.
+    pub(crate) fn increase<R: Rule>(&mut self, pos: usize, search_list: &[CellRef<R>]) {
+        if let Some(i) = self.indices[pos] {
+            self.sift_up(i, search_list);
+        }
+    }
+
+    /// Returns the position of the unknown cell with the highest activity,
+    /// dropping the known cells before it.
+    pub(crate) fn peek_unknown<R: Rule>(&mut self, search_list: &[CellRef<R>]) -> Option<usize> {
+        while let Some(&pos) = self.heap.first() {
+            if search_list[pos].state.get().is_none() {
+                return Some(pos);
+            }
+            self.heap.swap_remove(0);
+            self.indices[pos] = None;
+            if let Some(&first) = self.heap.first() {
+                self.indices[first] = Some(0);
+                self.sift_down(0, search_list);
+            }
+        }
+        None
+    }
+
+    /// Whether the position `a` should come before `b`.
+    fn before<R: Rule>(a: usize, b: usize, search_list: &[CellRef<R>]) -> bool {
+        let (activity_a, activity_b) =
+            (search_list[a].activity.get(), search_list[b].activity.get());
+        activity_a > activity_b || (activity_a >= activity_b && a < b)
+    }
+
+    /// Swaps two entries of the heap.
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.indices[self.heap[i]] = Some(i);
+        self.indices[self.heap[j]] = Some(j);
+    }
+
+    /// Moves an entry up until it comes after its parent.
+    fn sift_up<R: Rule>(&mut self, mut i: usize, search_list: &[CellRef<R>]) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !Self::before(self.heap[i], self.heap[parent], search_list) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    /// Moves an entry down until it comes before its children.
+    fn sift_down<R: Rule>(&mut self, mut i: usize, search_list: &[CellRef<R>]) {
+        loop {
+            let mut first = i;
+            for child in (2 * i + 1..).take(2) {
+                if child < self.heap.len()
+                    && Self::before(self.heap[child], self.heap[first], search_list)
+                {
+                    first = child;
+                }
+            }
+            if first == i {
+                break;
+            }
+            self.swap(i, first);
+            i = first;
+        }
+    }
+}
@@ -44,24 +44,53 @@
 //! o..o.oo...o..oo.$
 //! ............o..o!
 //! ```
+//!
+//! # Features
+//!
+//! * `apgcode`: computes the [apgcode](https://conwaylife.com/wiki/Apgcode)
+//!   of a result.
+//! * `serialize`: saves and loads the search with [serde](https://serde.rs).
+//! * `compressed`: saves the search in a compressed binary format.
+//!   Implies `serialize`.
+//! * `trace`: records the changes of the states of the cells,
+//!   so that the search can be replayed.
+//! * `stdweb` and `wasm-bindgen`: support for WASM targets.
+//!   Both imply `serialize`.
+//!
+//! None of the features are `no_std`-compatible, and neither is the core
+//! search: the rule parser [ca-rules](https://crates.io/crates/ca-rules)
+//! and the error type both require `std`.
 
 mod cells;
 mod config;
 mod error;
+mod heap;
+mod parallel;
 pub mod rules;
 mod search;
 mod traits;
 mod world;
 
+#[cfg(feature = "apgcode")]
+mod apgcode;
+
 #[cfg(feature = "serialize")]
 mod save;
 
-pub use cells::{State, ALIVE, DEAD};
-pub use config::{Config, NewState, SearchOrder, Symmetry, Transform};
+pub use cells::{CellState, State, ALIVE, DEAD};
+pub use config::{
+    Boundary, Config, Corner, CountMode, DecisionHeuristic, IncreasingResult, NewState,
+    SearchOrder, Symmetry, Transform, Triviality,
+};
 pub use error::Error;
-pub use search::Status;
-pub use traits::Search;
+pub use search::{NoneReason, ProgressCallback, SetReason, Statistics, Status};
+pub use traits::{OutputFormat, Search};
 pub use world::World;
 
+#[cfg(feature = "trace")]
+pub use search::TraceEvent;
+
+#[cfg(feature = "serialize")]
+pub use save::{SweepResult, WorldSer};
 #[cfg(feature = "serialize")]
-pub use save::WorldSer;
+pub use traits::{DedupResults, Results};
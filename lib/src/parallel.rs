@@ -0,0 +1,179 @@
+//! Parallel search.
+
+use crate::{
+    cells::{Coord, State},
+    config::{Config, SearchOrder},
+    error::Error,
+    rules::{NtLifeGen, Rule},
+    search::Status,
+    traits::Search,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Number of steps between two checks of whether another thread
+/// has found a result.
+const CHECK_INTERVAL: u64 = 10000;
+
+/// Number of branches for each thread.
+///
+/// More branches than threads, so that a thread that finishes early
+/// can take another branch.
+const BRANCHES_PER_THREAD: usize = 4;
+
+impl Config {
+    /// Splits the search into branches.
+    ///
+    /// The first few unknown cells in the search order are fixed to
+    /// every possible combination of states. Each branch is given as
+    /// the `known_cells` of a new configuration.
+    fn branches(&self, world: &dyn Search, threads: usize) -> Result<Vec<Config>, Error> {
+        let gen = if world.is_gen_rule() {
+            self.rule_string.parse::<NtLifeGen>()?.gen()
+        } else {
+            2
+        };
+        let mut unknown: Vec<Coord> = Vec::new();
+        for x in 0..self.width {
+            for y in 0..self.height {
+                for t in 0..self.period {
+                    if world.get_cell_state((x, y, t))?.is_none() {
+                        unknown.push((x, y, t));
+                    }
+                }
+            }
+        }
+        // Sorts in the same order as the `search_list` of the world,
+        // which starts from `start_corner`.
+        let order = self.auto_search_order();
+        unknown.sort_by_key(|&coord| {
+            let (x, y, t) = self.flip_to_start_corner(coord);
+            match order {
+                SearchOrder::RowFirst => (y, x, t),
+                SearchOrder::ColumnFirst => (x, y, t),
+                SearchOrder::Diagonal => (x + y, x, t),
+            }
+        });
+
+        let mut count = 1;
+        let mut cells = 0;
+        while count < threads * BRANCHES_PER_THREAD && cells < unknown.len() {
+            count *= gen;
+            cells += 1;
+        }
+
+        Ok((0..count)
+            .map(|mut n| {
+                let mut known_cells = self.known_cells.clone();
+                for &coord in unknown[..cells].iter() {
+                    known_cells.push((coord, State(n % gen)));
+                    n /= gen;
+                }
+                self.clone().set_known_cells(known_cells)
+            })
+            .collect())
+    }
+
+    /// Searches with several threads.
+    ///
+    /// The search tree is split by fixing the first few unknown cells
+    /// to different states. Each thread builds its own world from the
+    /// configuration and searches some of the branches, until one of
+    /// the threads finds a result. `max_step` applies to each branch.
+    ///
+    /// Returns `Found` and the world containing the result if a result
+    /// is found, `None` if such pattern does not exist, `Searching`
+    /// if no results are found within `max_step`.
+    /// All the cells in the returned world are given in `known_cells`,
+    /// so searching it again will not give any more results.
+    ///
+    /// Which result is found first may differ from run to run,
+    /// and from the result of `search`.
+    ///
+    /// Note that threads are not available on `wasm32-unknown-unknown`.
+    pub fn search_parallel(
+        &self,
+        threads: usize,
+        max_step: Option<u64>,
+    ) -> Result<(Status, Option<Box<dyn Search>>), Error> {
+        let world = self.world()?;
+        let branches = self.branches(world.as_ref(), threads.max(1))?;
+        let next_branch = AtomicUsize::new(0);
+        let searching = AtomicBool::new(false);
+        let found = AtomicBool::new(false);
+        let result: Mutex<Option<Vec<(Coord, State)>>> = Mutex::new(None);
+
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                scope.spawn(|| {
+                    while !found.load(Ordering::Relaxed) {
+                        let i = next_branch.fetch_add(1, Ordering::Relaxed);
+                        let config = match branches.get(i) {
+                            Some(config) => config,
+                            None => break,
+                        };
+                        // A branch that contradicts itself has no results.
+                        let mut world = match config.world() {
+                            Ok(world) => world,
+                            Err(_) => continue,
+                        };
+                        let mut step = 0;
+                        let status = loop {
+                            if found.load(Ordering::Relaxed) {
+                                break Status::Paused;
+                            }
+                            let interval = match max_step {
+                                Some(max) => CHECK_INTERVAL.min(max - step),
+                                None => CHECK_INTERVAL,
+                            };
+                            let status = world.search(Some(interval));
+                            step += interval;
+                            if status != Status::Searching
+                                || matches!(max_step, Some(max) if step >= max)
+                            {
+                                break status;
+                            }
+                        };
+                        match status {
+                            Status::Found if !found.swap(true, Ordering::Relaxed) => {
+                                *result.lock().unwrap() = Some(known_cells(world.as_ref()));
+                            }
+                            Status::Searching => searching.store(true, Ordering::Relaxed),
+                            _ => (),
+                        }
+                    }
+                });
+            }
+        });
+
+        if let Some(known_cells) = result.into_inner().unwrap() {
+            let world = self.clone().set_known_cells(known_cells).world()?;
+            Ok((Status::Found, Some(world)))
+        } else if searching.into_inner() {
+            Ok((Status::Searching, None))
+        } else {
+            Ok((Status::None, None))
+        }
+    }
+}
+
+/// The states of all the known cells in the world.
+fn known_cells(world: &dyn Search) -> Vec<(Coord, State)> {
+    let config = world.config();
+    let mut cells = Vec::new();
+    for x in 0..config.width {
+        for y in 0..config.height {
+            for t in 0..config.period {
+                if let Ok(Some(state)) = world.get_cell_state((x, y, t)) {
+                    cells.push(((x, y, t), state));
+                }
+            }
+        }
+    }
+    cells
+}
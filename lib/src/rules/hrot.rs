@@ -0,0 +1,560 @@
+//! Higher-range outer-totalistic rules.
+//!
+//! For the notation, please see
+//! [this article on LifeWiki](https://conwaylife.com/wiki/Higher-range_outer-totalistic_cellular_automaton).
+//!
+//! Only the Moore neighborhood (`NM`) with 2 states is supported for now.
+//! Other neighborhoods (von Neumann, hexagonal, etc.) and Generations
+//! HROT rules are left for the future.
+
+use crate::{
+    cells::{CellRef, State, ALIVE, DEAD},
+    error::Error,
+    rules::Rule,
+    search::Reason,
+    world::World,
+};
+use bitflags::bitflags;
+use ca_rules::ParseRuleError;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// The largest supported range.
+///
+/// The size of the implication table grows as the fourth power of the range.
+const MAX_RANGE: isize = 10;
+
+bitflags! {
+    /// Flags to imply the state of a cell and its neighbors.
+    #[derive(Default)]
+    struct ImplFlags: u8 {
+        /// A conflict is detected.
+        const CONFLICT = 0b_0000_0001;
+
+        /// The successor must be alive.
+        const SUCC_ALIVE = 0b_0000_0100;
+
+        /// The successor must be dead.
+        const SUCC_DEAD = 0b_0000_1000;
+
+        /// The state of the successor is implied.
+        const SUCC = Self::SUCC_ALIVE.bits | Self::SUCC_DEAD.bits;
+
+        /// The cell itself must be alive.
+        const SELF_ALIVE = 0b_0001_0000;
+
+        /// The cell itself must be dead.
+        const SELF_DEAD = 0b_0010_0000;
+
+        /// The state of the cell itself is implied.
+        const SELF = Self::SELF_ALIVE.bits | Self::SELF_DEAD.bits;
+
+        /// All unknown neighbors must be alive.
+        const NBHD_ALIVE = 0b_0100_0000;
+
+        /// All unknown neighbors must be dead.
+        const NBHD_DEAD = 0b_1000_0000;
+
+        /// The states of all unknown neighbors are implied.
+        const NBHD = Self::NBHD_ALIVE.bits | Self::NBHD_DEAD.bits;
+    }
+}
+
+/// The neighborhood descriptor.
+///
+/// It is a 28-bit integer of the form `0b_a_b_ij_kl`,
+/// where:
+///
+/// * `0b_a` (12 bits) is the number of dead cells in the neighborhood.
+/// * `0b_b` (12 bits) is the number of living cells in the neighborhood.
+/// * `0b_ij` is the state of the successor.
+/// * `0b_kl` is the state of the cell itself.
+///
+/// For `0b_ij` and `0b_kl`:
+/// * `0b_10` means dead,
+/// * `0b_01` means alive,
+/// * `0b_00` means unknown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NbhdDesc(u32);
+
+/// Higher-range outer-totalistic rules with the Moore neighborhood,
+/// e.g., `R2,C2,S6-11,B9-11,NM`.
+#[derive(Clone)]
+pub struct Hrot {
+    /// The range of the neighborhood.
+    range: isize,
+    /// Whether the cell itself is counted in the neighborhood (`M1`).
+    middle: bool,
+    /// The birth conditions, as given in the rule string.
+    b: Vec<usize>,
+    /// The survival conditions, as given in the rule string.
+    s: Vec<usize>,
+    /// Number of cells in the neighborhood, not including the cell itself.
+    size: usize,
+    /// Whether the rule contains `B0`.
+    b0: bool,
+    /// Whether a living cell survives when all its neighbors are alive.
+    s_max: bool,
+    /// An array of actions for all neighborhood descriptors.
+    impl_table: Vec<ImplFlags>,
+}
+
+impl Hrot {
+    /// Constructs a new rule from the range, whether the cell itself
+    /// is counted, and the birth and survival conditions.
+    ///
+    /// Conditions larger than the size of the neighborhood are ignored.
+    pub fn new(range: isize, middle: bool, b: Vec<usize>, s: Vec<usize>) -> Self {
+        let size = ((2 * range + 1) * (2 * range + 1) - 1) as usize;
+
+        // With `M1`, a living cell counts itself, so the survival
+        // conditions are shifted by one.
+        let births: Vec<usize> = b.clone();
+        let survivals: Vec<usize> = if middle {
+            s.iter().filter(|&&n| n > 0).map(|&n| n - 1).collect()
+        } else {
+            s.clone()
+        };
+
+        let b0 = births.contains(&0);
+        let s_max = survivals.contains(&size);
+
+        let impl_table = vec![ImplFlags::empty(); ((size + 1) * (size + 1)) << 4];
+
+        Hrot {
+            range,
+            middle,
+            b,
+            s,
+            size,
+            b0,
+            s_max,
+            impl_table,
+        }
+        .init_trans(births, survivals)
+        .init_conflict()
+        .init_impl()
+        .init_impl_nbhd()
+    }
+
+    /// The index in the `impl_table` of the neighborhood with the given
+    /// numbers of dead and living cells.
+    fn index(&self, dead: usize, alive: usize) -> usize {
+        (dead * (self.size + 1) + alive) << 4
+    }
+
+    /// The index in the `impl_table` of a neighborhood descriptor.
+    fn desc_index(&self, desc: NbhdDesc) -> usize {
+        let dead = (desc.0 >> 16) as usize;
+        let alive = (desc.0 >> 4 & 0xfff) as usize;
+        self.index(dead, alive) | (desc.0 & 0xf) as usize
+    }
+
+    /// Deduces the implication for the successor.
+    fn init_trans(mut self, b: Vec<usize>, s: Vec<usize>) -> Self {
+        let size = self.size;
+
+        // Fills in the positions of the neighborhood descriptors
+        // that have no unknown neighbors.
+        for alives in 0..=size {
+            let desc = self.index(size - alives, alives);
+            self.impl_table[desc | 0b10] |= if b.contains(&alives) {
+                ImplFlags::SUCC_ALIVE
+            } else {
+                ImplFlags::SUCC_DEAD
+            };
+            self.impl_table[desc | 0b01] |= if s.contains(&alives) {
+                ImplFlags::SUCC_ALIVE
+            } else {
+                ImplFlags::SUCC_DEAD
+            };
+            self.impl_table[desc] |= if b.contains(&alives) && s.contains(&alives) {
+                ImplFlags::SUCC_ALIVE
+            } else if !b.contains(&alives) && !s.contains(&alives) {
+                ImplFlags::SUCC_DEAD
+            } else {
+                ImplFlags::empty()
+            };
+        }
+
+        // Fills in other positions.
+        for unknowns in 1..=size {
+            for alives in 0..=size - unknowns {
+                let desc = self.index(size - alives - unknowns, alives);
+                let desc0 = self.index(size - alives - unknowns + 1, alives);
+                let desc1 = self.index(size - alives - unknowns, alives + 1);
+
+                for state in 0..=2 {
+                    let trans0 = self.impl_table[desc0 | state];
+
+                    if trans0 == self.impl_table[desc1 | state] {
+                        self.impl_table[desc | state] |= trans0;
+                    }
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Deduces the conflicts.
+    fn init_conflict(mut self) -> Self {
+        for deads in 0..=self.size {
+            for alives in 0..=self.size - deads {
+                for state in 0..=2 {
+                    let desc = self.index(deads, alives) | state;
+
+                    if self.impl_table[desc].contains(ImplFlags::SUCC_ALIVE) {
+                        self.impl_table[desc | 0b10 << 2] = ImplFlags::CONFLICT;
+                    } else if self.impl_table[desc].contains(ImplFlags::SUCC_DEAD) {
+                        self.impl_table[desc | 0b01 << 2] = ImplFlags::CONFLICT;
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    /// Deduces the implication for the cell itself.
+    fn init_impl(mut self) -> Self {
+        let size = self.size;
+        for unknowns in 0..=size {
+            for alives in 0..=size - unknowns {
+                let desc = self.index(size - alives - unknowns, alives);
+
+                for succ_state in 1..=2 {
+                    let flag = if succ_state == 0b10 {
+                        ImplFlags::SUCC_ALIVE | ImplFlags::CONFLICT
+                    } else {
+                        ImplFlags::SUCC_DEAD | ImplFlags::CONFLICT
+                    };
+
+                    let possibly_dead = !self.impl_table[desc | 0b10].intersects(flag);
+                    let possibly_alive = !self.impl_table[desc | 0b01].intersects(flag);
+
+                    let index = desc | succ_state << 2;
+                    if possibly_dead && !possibly_alive {
+                        self.impl_table[index] |= ImplFlags::SELF_DEAD;
+                    } else if !possibly_dead && possibly_alive {
+                        self.impl_table[index] |= ImplFlags::SELF_ALIVE;
+                    } else if !possibly_dead && !possibly_alive {
+                        self.impl_table[index] = ImplFlags::CONFLICT;
+                    }
+                }
+            }
+        }
+
+        self
+    }
+
+    ///  Deduces the implication for the neighbors.
+    fn init_impl_nbhd(mut self) -> Self {
+        let size = self.size;
+        for unknowns in 1..=size {
+            for alives in 0..=size - unknowns {
+                let desc = self.index(size - alives - unknowns, alives);
+                let desc0 = self.index(size - alives - unknowns + 1, alives);
+                let desc1 = self.index(size - alives - unknowns, alives + 1);
+
+                for succ_state in 1..=2 {
+                    let flag = if succ_state == 0b10 {
+                        ImplFlags::SUCC_ALIVE | ImplFlags::CONFLICT
+                    } else {
+                        ImplFlags::SUCC_DEAD | ImplFlags::CONFLICT
+                    };
+
+                    let index = desc | succ_state << 2;
+
+                    for state in 0..=2 {
+                        let possibly_dead = !self.impl_table[desc0 | state].intersects(flag);
+                        let possibly_alive = !self.impl_table[desc1 | state].intersects(flag);
+
+                        if possibly_dead && !possibly_alive {
+                            self.impl_table[index | state] |= ImplFlags::NBHD_DEAD;
+                        } else if !possibly_dead && possibly_alive {
+                            self.impl_table[index | state] |= ImplFlags::NBHD_ALIVE;
+                        } else if !possibly_dead && !possibly_alive {
+                            self.impl_table[index | state] = ImplFlags::CONFLICT;
+                        }
+                    }
+                }
+            }
+        }
+
+        self
+    }
+}
+
+/// Parses a list of counts such as `6-11` or `6..11` into `list`.
+///
+/// Counts larger than the largest possible neighborhood (including the
+/// cell itself) can never be reached, so the ranges are cut off there.
+fn parse_counts(input: &str, list: &mut Vec<usize>) -> Result<(), ParseRuleError> {
+    let number = |s: &str| {
+        s.parse::<usize>()
+            .map_err(|_| ParseRuleError::MissingNumber)
+    };
+    let (from, to) = if let Some(i) = input.find("..") {
+        (number(&input[..i])?, number(&input[i + 2..])?)
+    } else if let Some(i) = input.find('-') {
+        (number(&input[..i])?, number(&input[i + 1..])?)
+    } else {
+        let n = number(input)?;
+        (n, n)
+    };
+    let max_count = ((2 * MAX_RANGE + 1) * (2 * MAX_RANGE + 1)) as usize;
+    list.extend(from..=to.min(max_count));
+    Ok(())
+}
+
+impl FromStr for Hrot {
+    type Err = Error;
+
+    /// Parses a rule string in the form `R2,C2,M0,S6-11,B9-11,NM`.
+    ///
+    /// `C` (number of states), `M` (whether the cell itself is counted)
+    /// and `N` (neighborhood) are optional. Ranges can be written as
+    /// `6-11` or `6..11`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut range = None;
+        let mut middle = false;
+        let mut b = None;
+        let mut s = None;
+        // Whether the numbers without a prefix belong to `b` or `s`.
+        let mut in_b = None;
+
+        // Position of the current item in the rule string.
+        let mut position = 0;
+        for part in input.split(',') {
+            let at = |error| Error::RuleSyntaxError(position, error);
+            let mut chars = part.chars();
+            let first = chars
+                .next()
+                .ok_or_else(|| at(ParseRuleError::MissingNumber))?;
+            let rest = chars.as_str();
+            match first.to_ascii_uppercase() {
+                'R' => {
+                    let n = rest
+                        .parse::<isize>()
+                        .map_err(|_| at(ParseRuleError::MissingNumber))?;
+                    if !(1..=MAX_RANGE).contains(&n) {
+                        return Err(Error::HrotError("the range must be between 1 and 10"));
+                    }
+                    range = Some(n);
+                    in_b = None;
+                }
+                'C' => {
+                    let n = rest
+                        .parse::<usize>()
+                        .map_err(|_| at(ParseRuleError::MissingNumber))?;
+                    if n > 2 {
+                        return Err(Error::HrotError("Generations rules are not supported yet"));
+                    }
+                    in_b = None;
+                }
+                'M' => {
+                    middle = match rest {
+                        "0" => false,
+                        "1" => true,
+                        _ => return Err(at(ParseRuleError::MissingNumber)),
+                    };
+                    in_b = None;
+                }
+                'N' => {
+                    if !rest.eq_ignore_ascii_case("M") {
+                        return Err(Error::HrotError(
+                            "only the Moore neighborhood is supported yet",
+                        ));
+                    }
+                    in_b = None;
+                }
+                'B' => {
+                    let list = b.get_or_insert_with(Vec::new);
+                    if !rest.is_empty() {
+                        parse_counts(rest, list).map_err(at)?;
+                    }
+                    in_b = Some(true);
+                }
+                'S' => {
+                    let list = s.get_or_insert_with(Vec::new);
+                    if !rest.is_empty() {
+                        parse_counts(rest, list).map_err(at)?;
+                    }
+                    in_b = Some(false);
+                }
+                c if c.is_ascii_digit() => match in_b {
+                    Some(true) => parse_counts(part, b.as_mut().unwrap()).map_err(at)?,
+                    Some(false) => parse_counts(part, s.as_mut().unwrap()).map_err(at)?,
+                    None => return Err(at(ParseRuleError::Unexpected(c))),
+                },
+                c => return Err(at(ParseRuleError::Unexpected(c))),
+            }
+            position += part.chars().count() + 1;
+        }
+
+        let range = range.ok_or(ParseRuleError::Missing('R'))?;
+        let b = b.ok_or(ParseRuleError::Missing('B'))?;
+        let s = s.ok_or(ParseRuleError::Missing('S'))?;
+        let rule = Hrot::new(range, middle, b, s);
+        if rule.has_b0_s8() {
+            Err(Error::B0S8Error)
+        } else {
+            Ok(rule)
+        }
+    }
+}
+
+impl Rule for Hrot {
+    type Desc = NbhdDesc;
+
+    const IS_GEN: bool = false;
+
+    fn has_b0(&self) -> bool {
+        self.b0
+    }
+
+    fn has_b0_s8(&self) -> bool {
+        self.b0 && self.s_max
+    }
+
+    fn gen(&self) -> usize {
+        2
+    }
+
+    fn nbhd(&self) -> Vec<(isize, isize)> {
+        let r = self.range;
+        (-r..=r)
+            .flat_map(|x| (-r..=r).map(move |y| (x, y)))
+            .filter(|&(x, y)| (x, y) != (0, 0))
+            .collect()
+    }
+
+    fn new_desc(&self, state: State, succ_state: State) -> Self::Desc {
+        let nbhd_state = match state {
+            ALIVE => self.size << 4,
+            _ => self.size << 16,
+        } as u32;
+        let succ_state = match succ_state {
+            ALIVE => 0b01,
+            _ => 0b10,
+        };
+        let state = match state {
+            ALIVE => 0b01,
+            _ => 0b10,
+        };
+        NbhdDesc(nbhd_state | succ_state << 2 | state)
+    }
+
+    fn update_desc(cell: CellRef<Self>, state: Option<State>, new: bool) {
+        let state_num = match state {
+            Some(ALIVE) => 1 << 4,
+            Some(_) => 1 << 16,
+            None => 0,
+        };
+        for &neigh in cell.nbhd.iter() {
+            let neigh = neigh.unwrap();
+            let mut desc = neigh.desc.get();
+            if new {
+                desc.0 += state_num;
+            } else {
+                desc.0 -= state_num;
+            }
+            neigh.desc.set(desc);
+        }
+        let change_num = match state {
+            Some(ALIVE) => 0b01,
+            Some(_) => 0b10,
+            _ => 0,
+        };
+        if let Some(pred) = cell.pred {
+            let mut desc = pred.desc.get();
+            desc.0 ^= change_num << 2;
+            pred.desc.set(desc);
+        }
+        let mut desc = cell.desc.get();
+        desc.0 ^= change_num;
+        cell.desc.set(desc);
+    }
+
+    fn consistify<'a>(world: &mut World<'a, Self>, cell: CellRef<'a, Self>) -> bool {
+        let flags = world.rule.impl_table[world.rule.desc_index(cell.desc.get())];
+        if flags.is_empty() {
+            return true;
+        }
+        if flags.contains(ImplFlags::CONFLICT) {
+            return false;
+        }
+        if flags.intersects(ImplFlags::SUCC) {
+            let state = if flags.contains(ImplFlags::SUCC_DEAD) {
+                DEAD
+            } else {
+                ALIVE
+            };
+            let succ = cell.succ.unwrap();
+            return world.set_cell(succ, state, Reason::Deduce);
+        }
+        if flags.intersects(ImplFlags::SELF) {
+            let state = if flags.contains(ImplFlags::SELF_DEAD) {
+                DEAD
+            } else {
+                ALIVE
+            };
+            if !world.set_cell(cell, state, Reason::Deduce) {
+                return false;
+            }
+        }
+        if flags.intersects(ImplFlags::NBHD) {
+            let state = if flags.contains(ImplFlags::NBHD_DEAD) {
+                DEAD
+            } else {
+                ALIVE
+            };
+            for &neigh in cell.nbhd.iter() {
+                if let Some(neigh) = neigh {
+                    if neigh.state.get().is_none() && !world.set_cell(neigh, state, Reason::Deduce)
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Writes a list of counts, merging consecutive numbers into ranges.
+fn write_counts(f: &mut Formatter, counts: &[usize]) -> fmt::Result {
+    let mut counts = counts.to_vec();
+    counts.sort_unstable();
+    counts.dedup();
+    let mut i = 0;
+    while i < counts.len() {
+        let mut j = i;
+        while j + 1 < counts.len() && counts[j + 1] == counts[j] + 1 {
+            j += 1;
+        }
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        if i == j {
+            write!(f, "{}", counts[i])?;
+        } else {
+            write!(f, "{}-{}", counts[i], counts[j])?;
+        }
+        i = j + 1;
+    }
+    Ok(())
+}
+
+/// Displays the rule in the canonical form, e.g., `R2,C2,M0,S6-11,B9-11,NM`.
+impl Display for Hrot {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "R{},C2,M{},S", self.range, self.middle as u8)?;
+        write_counts(f, &self.s)?;
+        write!(f, ",B")?;
+        write_counts(f, &self.b)?;
+        write!(f, ",NM")
+    }
+}
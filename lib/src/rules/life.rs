@@ -9,7 +9,10 @@ use crate::{
 };
 use bitflags::bitflags;
 use ca_rules::{ParseLife, ParseLifeGen};
-use std::str::FromStr;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 bitflags! {
     /// Flags to imply the state of a cell and its neighbors.
@@ -136,11 +139,18 @@ impl Life {
 
         let impl_table = [ImplFlags::empty(); 1 << 12];
 
-        Life { b0, s8, impl_table }
-            .init_trans(b, s)
-            .init_conflict()
-            .init_impl()
-            .init_impl_nbhd()
+        Life {
+            b: b.clone(),
+            s: s.clone(),
+            b0,
+            s8,
+            impl_table,
+            odd: None,
+        }
+        .init_trans(b, s)
+        .init_conflict()
+        .init_impl()
+        .init_impl_nbhd()
     }
 
     /// Deduces the implication for the successor.
@@ -272,3 +282,35 @@ impl Life {
         self
     }
 }
+
+/// Writes the `b` and `s` data in the form `B3/S23`.
+fn write_bs(f: &mut Formatter, b: &[u8], s: &[u8]) -> fmt::Result {
+    write!(f, "B")?;
+    for i in (0..=8).filter(|i| b.contains(i)) {
+        write!(f, "{}", i)?;
+    }
+    write!(f, "/S")?;
+    for i in (0..=8).filter(|i| s.contains(i)) {
+        write!(f, "{}", i)?;
+    }
+    Ok(())
+}
+
+/// Displays the rule in the canonical form, e.g., `B3/S23`.
+impl Display for Life {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_bs(f, &self.b, &self.s)?;
+        if let Some(odd) = &self.odd {
+            write!(f, "|{}", odd)?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays the rule in the canonical form, e.g., `B3/S23/C3`.
+impl Display for LifeGen {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_bs(f, &self.b, &self.s)?;
+        write!(f, "/C{}", self.gen)
+    }
+}
@@ -46,14 +46,64 @@ macro_rules! impl_rule {
         #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
         pub struct NbhdDesc($desc_type);
 
+        impl NbhdDesc {
+            /// Generates a neighborhood descriptor which says that all neighboring
+            /// cells have states `state`, and the successor has state `succ_state`.
+            fn new(state: State, succ_state: State) -> Self {
+                let nbhd_state = match state {
+                    ALIVE => $alive_desc,
+                    _ => $dead_desc,
+                };
+                let succ_state = match succ_state {
+                    ALIVE => 0b01,
+                    _ => 0b10,
+                };
+                let state = match state {
+                    ALIVE => 0b01,
+                    _ => 0b10,
+                };
+                NbhdDesc(nbhd_state << 4 | succ_state << 2 | state)
+            }
+        }
+
         $(#[$doc])*
+        #[derive(Clone)]
         pub struct $rule {
+            /// The `b` data of the rule, as given by the parser.
+            b: Vec<u8>,
+            /// The `s` data of the rule, as given by the parser.
+            s: Vec<u8>,
             /// Whether the rule contains `B0`.
             b0: bool,
             /// Whether the rule contains `S8`.
             s8: bool,
             /// An array of actions for all neighborhood descriptors.
             impl_table: $impl_table,
+            /// The rule for cells with odd `x + y`, in a checkerboard rule.
+            ///
+            /// `None` means that all cells follow the same rule.
+            odd: Option<Box<$rule>>,
+        }
+
+        impl $rule {
+            /// Combines two rules into a checkerboard rule, where cells
+            /// with even `x + y` follow `self`, and cells with odd `x + y`
+            /// follow `odd`.
+            ///
+            /// Returns an error if either rule contains `B0`, or is already
+            /// a checkerboard rule.
+            pub fn checkerboard(self, odd: $rule) -> Result<Self, Error> {
+                if self.b0 || odd.b0 {
+                    return Err(Error::CheckerboardB0Error);
+                }
+                if self.odd.is_some() || odd.odd.is_some() {
+                    return Err(Error::CheckerboardNestedError);
+                }
+                Ok($rule {
+                    odd: Some(Box::new(odd)),
+                    ..self
+                })
+            }
         }
 
         /// A parser for the rule.
@@ -67,8 +117,26 @@ macro_rules! impl_rule {
             type Err = Error;
 
             fn from_str(input: &str) -> Result<Self, Self::Err> {
+                // A checkerboard rule, e.g., `B3/S23|B36/S23`.
+                if let Some((even, odd)) = input.split_once('|') {
+                    let offset = even.chars().count() + 1;
+                    let odd = odd.parse().map_err(|e| match e {
+                        Error::RuleSyntaxError(i, e) => Error::RuleSyntaxError(i + offset, e),
+                        e => e,
+                    })?;
+                    return even.parse::<$rule>()?.checkerboard(odd);
+                }
                 let rule: $rule = $parser::parse_rule(input)
-                    .map_err(Error::ParseRuleError)?;
+                    .or_else(|e| {
+                        super::normalize_bs(input)
+                            .and_then(|input| $parser::parse_rule(&input).ok())
+                            .ok_or(e)
+                    })
+                    .map_err(|e| {
+                        super::locate_error(input, e, |input| {
+                            <super::Syntax as $parser>::parse_rule(input).map(|_| ())
+                        })
+                    })?;
                 if rule.has_b0_s8() {
                     Err(Error::B0S8Error)
                 } else {
@@ -90,24 +158,16 @@ macro_rules! impl_rule {
                 self.b0 && self.s8
             }
 
+            fn is_checkerboard(&self) -> bool {
+                self.odd.is_some()
+            }
+
             fn gen(&self) -> usize {
                 2
             }
 
-            fn new_desc(state: State, succ_state: State) -> Self::Desc {
-                let nbhd_state = match state {
-                    ALIVE => $alive_desc,
-                    _ => $dead_desc,
-                };
-                let succ_state = match succ_state {
-                    ALIVE => 0b01,
-                    _ => 0b10,
-                };
-                let state = match state {
-                    ALIVE => 0b01,
-                    _ => 0b10,
-                };
-                NbhdDesc(nbhd_state << 4 | succ_state << 2 | state)
+            fn new_desc(&self, state: State, succ_state: State) -> Self::Desc {
+                NbhdDesc::new(state, succ_state)
             }
 
             fn update_desc(
@@ -132,7 +192,11 @@ macro_rules! impl_rule {
             }
 
             fn consistify<$a>($world: &mut World<$a, Self>, $cell_cons: CellRef<$a, Self>) -> bool {
-                let $flags = $world.rule.impl_table[$cell_cons.desc.get().0 as usize];
+                let rule = match &$world.rule.odd {
+                    Some(odd) if ($cell_cons.coord.0 + $cell_cons.coord.1).rem_euclid(2) == 1 => odd,
+                    _ => &$world.rule,
+                };
+                let $flags = rule.impl_table[$cell_cons.desc.get().0 as usize];
                 if $flags.is_empty() {
                     return true;
                 }
@@ -173,7 +237,12 @@ macro_rules! impl_rule {
         pub struct NbhdDescGen ($desc_type, Option<State>);
 
         $(#[$doc_gen])*
+        #[derive(Clone)]
         pub struct $rule_gen {
+            /// The `b` data of the rule, as given by the parser.
+            b: Vec<u8>,
+            /// The `s` data of the rule, as given by the parser.
+            s: Vec<u8>,
             /// Whether the rule contains `B0`.
             b0: bool,
             /// Whether the rule contains `S8`.
@@ -191,6 +260,8 @@ macro_rules! impl_rule {
                 let life = $rule::new(b, s);
                 let impl_table = life.impl_table;
                 Self {
+                    b: life.b,
+                    s: life.s,
                     b0: life.b0,
                     s8: life.s8,
                     gen,
@@ -201,9 +272,12 @@ macro_rules! impl_rule {
             /// Converts to the corresponding non-Generations rule.
             pub fn non_gen(self) -> $rule {
                 $rule {
+                    b: self.b,
+                    s: self.s,
                     b0: self.b0,
                     s8: self.s8,
                     impl_table: self.impl_table,
+                    odd: None,
                 }
             }
         }
@@ -220,7 +294,16 @@ macro_rules! impl_rule {
 
             fn from_str(input: &str) -> Result<Self, Self::Err> {
                 let rule: $rule_gen = $parser_gen::parse_rule(input)
-                    .map_err(Error::ParseRuleError)?;
+                    .or_else(|e| {
+                        super::normalize_bs(input)
+                            .and_then(|input| $parser_gen::parse_rule(&input).ok())
+                            .ok_or(e)
+                    })
+                    .map_err(|e| {
+                        super::locate_error(input, e, |input| {
+                            <super::Syntax as $parser_gen>::parse_rule(input).map(|_| ())
+                        })
+                    })?;
                 if rule.has_b0_s8() {
                     Err(Error::B0S8Error)
                 } else {
@@ -247,8 +330,8 @@ macro_rules! impl_rule {
                 self.gen
             }
 
-            fn new_desc(state: State, succ_state: State) -> Self::Desc {
-                let desc = $rule::new_desc(state, succ_state);
+            fn new_desc(&self, state: State, succ_state: State) -> Self::Desc {
+                let desc = NbhdDesc::new(state, succ_state);
                 NbhdDescGen(desc.0, Some(succ_state))
             }
 
@@ -324,17 +407,14 @@ macro_rules! impl_rule {
                         }
                     }
                     None => match desc.1 {
-                        Some(DEAD) => {
-                            if $flags_gen.contains(ImplFlags::SELF_ALIVE) {
-                                return $world_gen.set_cell(
-                                    $cell_cons_gen,
-                                    State(gen - 1),
-                                    Reason::Deduce
-                                );
-                            } else {
-                                return true;
-                            }
+                        Some(DEAD) if $flags_gen.contains(ImplFlags::SELF_ALIVE) => {
+                            return $world_gen.set_cell(
+                                $cell_cons_gen,
+                                State(gen - 1),
+                                Reason::Deduce
+                            );
                         }
+                        Some(DEAD) => return true,
                         Some(ALIVE) => {
                             if $flags_gen.intersects(ImplFlags::SELF) {
                                 let state = if $flags_gen.contains(ImplFlags::SELF_DEAD) {
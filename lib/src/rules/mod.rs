@@ -5,18 +5,38 @@
 
 mod macros;
 
+mod hrot;
 mod life;
 mod ntlife;
 
 use crate::{
     cells::{CellRef, State},
+    error::Error,
     world::World,
 };
+pub use ca_rules::ParseRuleError;
+use ca_rules::{ParseLife, ParseLifeGen, ParseNtLife, ParseNtLifeGen};
+pub use hrot::Hrot;
 pub use life::{Life, LifeGen};
 pub use ntlife::{NtLife, NtLifeGen};
 
+/// The relative positions of the eight cells in the Moore neighborhood.
+///
+/// The order matters for rules whose neighborhood descriptors
+/// record the position of each neighbor, e.g., `NtLife`.
+pub(crate) const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
 /// A cellular automaton rule.
-pub trait Rule: Sized {
+pub trait Rule: Sized + Clone + 'static {
     /// The type of neighborhood descriptor of the rule.
     ///
     /// It describes the states of the successor and neighbors of a cell,
@@ -41,12 +61,27 @@ pub trait Rule: Sized {
     /// generation, if all its neighbors in this generation are alive.
     fn has_b0_s8(&self) -> bool;
 
+    /// Whether the rule is a checkerboard rule, where cells with
+    /// odd `x + y` follow a different rule.
+    fn is_checkerboard(&self) -> bool {
+        false
+    }
+
     /// The number of states.
     fn gen(&self) -> usize;
 
+    /// The relative positions of the neighbors of a cell.
+    ///
+    /// The world keeps a border of background cells around the search
+    /// range, as wide as the farthest neighbor. The default is the
+    /// Moore neighborhood.
+    fn nbhd(&self) -> Vec<(isize, isize)> {
+        MOORE.to_vec()
+    }
+
     /// Generates a neighborhood descriptor which says that all neighboring
     /// cells have states `state`, and the successor has state `succ_state`.
-    fn new_desc(state: State, succ_state: State) -> Self::Desc;
+    fn new_desc(&self, state: State, succ_state: State) -> Self::Desc;
 
     /// Updates the neighborhood descriptors of all neighbors and the predecessor
     /// when the state of one cell is changed.
@@ -66,3 +101,178 @@ pub trait Rule: Sized {
     /// `true` if the cells are consistent.
     fn consistify<'a>(world: &mut World<'a, Self>, cell: CellRef<'a, Self>) -> bool;
 }
+
+/// Rewrites some common variants of rule strings that are not accepted
+/// by the parser:
+///
+/// * Commas instead of slashes, e.g., `B3,S23,C3`;
+/// * Survival conditions before birth conditions, e.g., `S23/B3` or `s23b3`.
+///
+/// Returns `None` if the rule string is not one of these variants.
+fn normalize_bs(input: &str) -> Option<String> {
+    let input = input.replace(',', "/");
+    if input.starts_with('S') || input.starts_with('s') {
+        let b_start = input.find(['B', 'b'])?;
+        let s = input[..b_start].trim_end_matches('/');
+        let (b, rest) = match input[b_start..].find('/') {
+            Some(i) => input[b_start..].split_at(i),
+            None => (&input[b_start..], ""),
+        };
+        Some(format!("{}/{}{}", b, s, rest))
+    } else if input.contains('/') {
+        Some(input)
+    } else {
+        None
+    }
+}
+
+/// The result of parsing a rule string without building the rule,
+/// which is much faster. Used by `locate_error`.
+struct Syntax;
+
+impl ParseLife for Syntax {
+    fn from_bs(_: Vec<u8>, _: Vec<u8>) -> Self {
+        Syntax
+    }
+}
+
+impl ParseLifeGen for Syntax {
+    fn from_bsg(_: Vec<u8>, _: Vec<u8>, _: usize) -> Self {
+        Syntax
+    }
+}
+
+impl ParseNtLife for Syntax {
+    fn from_bs(_: Vec<u8>, _: Vec<u8>) -> Self {
+        Syntax
+    }
+}
+
+impl ParseNtLifeGen for Syntax {
+    fn from_bsg(_: Vec<u8>, _: Vec<u8>, _: usize) -> Self {
+        Syntax
+    }
+}
+
+/// Adds the position where a rule string fails to parse to an error
+/// of a parser from `ca_rules`, which does not report it.
+///
+/// `parse` is the parser that returned `error`. It reads the rule string
+/// from left to right, so the position is found by parsing parts of it:
+///
+/// * For `ExtraJunk`, it is the end of the longest prefix that parses.
+/// * For `Missing` and `MissingNumber`, it is the last position where
+///   inserting the missing character or a number changes the error.
+///
+/// Other errors are returned without a position.
+fn locate_error<F>(input: &str, error: ParseRuleError, parse: F) -> Error
+where
+    F: Fn(&str) -> Result<(), ParseRuleError>,
+{
+    let insert = match error {
+        ParseRuleError::ExtraJunk => None,
+        ParseRuleError::Missing(c) => Some(c),
+        ParseRuleError::MissingNumber => Some('3'),
+        _ => return Error::ParseRuleError(error),
+    };
+    let prefixes = input
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(Some(input.len()))
+        .enumerate();
+    let mut position = None;
+    for (position_in_chars, i) in prefixes {
+        let prefix = &input[..i];
+        let fixed = match insert {
+            None => parse(prefix).is_ok(),
+            Some(c) => parse(&format!("{}{}", prefix, c)).err() != Some(error.clone()),
+        };
+        if fixed {
+            position = Some(position_in_chars);
+        }
+    }
+    match position {
+        Some(position) => Error::RuleSyntaxError(position, error),
+        None => Error::ParseRuleError(error),
+    }
+}
+
+/// Whether a rule string looks like an HROT rule, i.e., starts with `R`
+/// followed by a number.
+///
+/// Used to decide whether to report the parse error for `Hrot`
+/// or for the other rules.
+pub(crate) fn is_hrot(input: &str) -> bool {
+    let mut chars = input.chars();
+    matches!(chars.next(), Some('R') | Some('r'))
+        && matches!(chars.next(), Some(c) if c.is_ascii_digit())
+}
+
+/// Something to do with a rule of any type, e.g., creating a world.
+///
+/// Closures cannot be generic over the type of the rule,
+/// so `with_rule` takes this instead.
+pub(crate) trait WithRule {
+    /// The result.
+    type Output;
+
+    /// Does something with the rule.
+    fn call<R: Rule>(self, rule: R) -> Result<Self::Output, Error>;
+}
+
+/// Parses a rule string, and passes the rule to `f`.
+///
+/// This is the only place that decides which type of rule a rule string
+/// is parsed as. The rule string is parsed as the first of `Life`,
+/// `NtLife`, `LifeGen`, `Hrot` and `NtLifeGen` that accepts it.
+/// `Hrot` is only tried if the rule string looks like an HROT rule, and
+/// its error is reported in that case. The error of `NtLife` is reported
+/// for checkerboard rules, and the error of `NtLifeGen` otherwise.
+///
+/// Checkerboard rules, e.g., `B3/S23|B36/S23`, are parsed as `Life` or
+/// `NtLife`. Generations rules with only 2 states are converted to the
+/// corresponding non-Generations rules.
+pub(crate) fn with_rule<F: WithRule>(rule_string: &str, f: F) -> Result<F::Output, Error> {
+    if let Ok(rule) = rule_string.parse::<Life>() {
+        f.call(rule)
+    } else if let Ok(rule) = rule_string.parse::<NtLife>() {
+        f.call(rule)
+    } else if let Ok(rule) = rule_string.parse::<LifeGen>() {
+        if rule.gen() > 2 {
+            f.call(rule)
+        } else {
+            f.call(rule.non_gen())
+        }
+    } else if is_hrot(rule_string) {
+        f.call(rule_string.parse::<Hrot>()?)
+    } else if rule_string.contains('|') {
+        f.call(rule_string.parse::<NtLife>()?)
+    } else {
+        let rule = rule_string.parse::<NtLifeGen>()?;
+        if rule.gen() > 2 {
+            f.call(rule)
+        } else {
+            f.call(rule.non_gen())
+        }
+    }
+}
+
+/// Only checks that the rule string can be parsed.
+struct ParseOnly;
+
+impl WithRule for ParseOnly {
+    type Output = ();
+
+    fn call<R: Rule>(self, _: R) -> Result<Self::Output, Error> {
+        Ok(())
+    }
+}
+
+/// Checks whether a rule string can be parsed as any supported rule,
+/// including checkerboard rules, e.g., `B3/S23|B36/S23`.
+///
+/// This accepts exactly the rule strings that `Config::world` accepts,
+/// and returns the same parse error otherwise. See `Config::rule_string`.
+pub fn validate_rule_string(rule_string: &str) -> Result<(), Error> {
+    with_rule(rule_string, ParseOnly)
+}
@@ -5,11 +5,14 @@ use crate::{
     error::Error,
     rules::Rule,
     search::Reason,
-    world::World,
+    world::{Point, World, ISOMETRIES},
 };
 use bitflags::bitflags;
 use ca_rules::{ParseNtLife, ParseNtLifeGen};
-use std::str::FromStr;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 bitflags! {
     /// Flags to imply the state of a cell and its neighbors.
@@ -134,11 +137,18 @@ impl NtLife {
 
         let impl_table = vec![ImplFlags::empty(); 1 << 20];
 
-        NtLife { b0, s8, impl_table }
-            .init_trans(b, s)
-            .init_conflict()
-            .init_impl()
-            .init_impl_nbhd()
+        NtLife {
+            b: b.clone(),
+            s: s.clone(),
+            b0,
+            s8,
+            impl_table,
+            odd: None,
+        }
+        .init_trans(b, s)
+        .init_conflict()
+        .init_impl()
+        .init_impl_nbhd()
     }
 
     /// Deduces the implication for the successor.
@@ -277,3 +287,207 @@ impl NtLife {
         self
     }
 }
+
+/// Letters in the [Hensel notation](https://conwaylife.com/wiki/Isotropic_non-totalistic_Life-like_cellular_automaton),
+/// and a neighborhood represented by each letter,
+/// for each number of living neighbors.
+///
+/// Other neighborhoods represented by the same letter
+/// are obtained by rotations and reflections.
+const HENSEL: [&[(char, u8)]; 9] = [
+    &[('c', 0x00)],
+    &[('c', 0x01), ('e', 0x02)],
+    &[
+        ('c', 0x05),
+        ('e', 0x0a),
+        ('k', 0x0c),
+        ('a', 0x03),
+        ('i', 0x18),
+        ('n', 0x24),
+    ],
+    &[
+        ('c', 0x25),
+        ('e', 0x1a),
+        ('k', 0x32),
+        ('a', 0x0b),
+        ('i', 0x07),
+        ('n', 0x0d),
+        ('y', 0x31),
+        ('q', 0x26),
+        ('j', 0x0e),
+        ('r', 0x19),
+    ],
+    &[
+        ('c', 0xa5),
+        ('e', 0x5a),
+        ('k', 0x33),
+        ('a', 0x0f),
+        ('i', 0x1d),
+        ('n', 0x27),
+        ('y', 0x35),
+        ('q', 0x36),
+        ('j', 0x3a),
+        ('r', 0x1b),
+        ('t', 0x39),
+        ('w', 0x2e),
+        ('z', 0x3c),
+    ],
+    &[
+        ('c', 0x5b),
+        ('e', 0xa7),
+        ('k', 0x75),
+        ('a', 0x2f),
+        ('i', 0x1f),
+        ('n', 0x3b),
+        ('y', 0x5d),
+        ('q', 0x3e),
+        ('j', 0x37),
+        ('r', 0x3d),
+    ],
+    &[
+        ('c', 0x5f),
+        ('e', 0xaf),
+        ('k', 0x77),
+        ('a', 0x3f),
+        ('i', 0xbd),
+        ('n', 0x7e),
+    ],
+    &[('c', 0x7f), ('e', 0xbf)],
+    &[('c', 0xff)],
+];
+
+/// Positions of the neighbors, from the highest bit to the lowest bit
+/// of the `b` / `s` data.
+const NBHD_POS: [Point; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// All the neighborhoods obtained from `nbhd` by rotations and reflections.
+fn orbit(nbhd: u8) -> Vec<u8> {
+    let mut orbit: Vec<u8> = ISOMETRIES
+        .iter()
+        .map(|f| {
+            (0..8)
+                .filter(|i| nbhd & 0x80 >> i != 0)
+                .map(|i| {
+                    let pos = f(NBHD_POS[i]);
+                    let j = NBHD_POS.iter().position(|&p| p == pos).unwrap();
+                    0x80 >> j
+                })
+                .fold(0, |n, bit| n | bit)
+        })
+        .collect();
+    orbit.sort_unstable();
+    orbit.dedup();
+    orbit
+}
+
+/// Writes the `b` or `s` data in the Hensel notation.
+///
+/// Returns `None` if the data is not isotropic.
+fn hensel(data: &[u8]) -> Option<String> {
+    let mut str = String::new();
+    for (n, letters) in HENSEL.iter().enumerate() {
+        let mut present = String::new();
+        let mut absent = String::new();
+        for &(letter, nbhd) in letters.iter() {
+            let orbit = orbit(nbhd);
+            let count = orbit.iter().filter(|n| data.contains(n)).count();
+            if count == orbit.len() {
+                present.push(letter);
+            } else if count == 0 {
+                absent.push(letter);
+            } else {
+                return None;
+            }
+        }
+        if present.is_empty() {
+            continue;
+        }
+        str.push_str(&n.to_string());
+        if absent.is_empty() {
+            continue;
+        } else if present.len() <= absent.len() {
+            str.push_str(&present);
+        } else {
+            str.push('-');
+            str.push_str(&absent);
+        }
+    }
+    Some(str)
+}
+
+/// Writes the `b` and `s` data in the
+/// [MAP format](https://conwaylife.com/wiki/Non-isotropic_Life-like_cellular_automaton).
+fn map(b: &[u8], s: &[u8]) -> String {
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut bits = (0..512).map(|k| {
+        let nbhd = ((k & 0x1e0) >> 1 | (k & 0x0f)) as u8;
+        if k & 0x10 == 0 {
+            b.contains(&nbhd)
+        } else {
+            s.contains(&nbhd)
+        }
+    });
+    let mut str = String::from("MAP");
+    loop {
+        let mut sextet = 0;
+        let mut len = 0;
+        for bit in bits.by_ref().take(6) {
+            sextet = sextet << 1 | bit as usize;
+            len += 1;
+        }
+        if len == 0 {
+            break;
+        }
+        str.push(BASE64[sextet << (6 - len)] as char);
+    }
+    str
+}
+
+/// Writes the `b` and `s` data in Hensel notation if the rule is
+/// isotropic, in MAP format otherwise.
+fn write_bs(f: &mut Formatter, b: &[u8], s: &[u8]) -> fmt::Result {
+    match (hensel(b), hensel(s)) {
+        (Some(b), Some(s)) => write!(f, "B{}/S{}", b, s),
+        _ => write!(f, "{}", map(b, s)),
+    }
+}
+
+/// Displays the rule in the canonical form.
+///
+/// Isotropic rules are written in Hensel notation, e.g., `B2ci3ai/S1e`.
+/// Other rules are written in MAP format. Hexagonal rules and rules with
+/// von Neumann neighborhoods are written as the equivalent rules in the
+/// Moore neighborhood.
+impl Display for NtLife {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_bs(f, &self.b, &self.s)?;
+        if let Some(odd) = &self.odd {
+            write!(f, "|{}", odd)?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays the rule in the canonical form.
+///
+/// Isotropic rules are written in Hensel notation, e.g., `B2ci3ai/S1e/C3`.
+/// Other rules are written in MAP format, e.g., `MAP.../3`.
+impl Display for NtLifeGen {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write_bs(f, &self.b, &self.s)?;
+        if hensel(&self.b).is_some() && hensel(&self.s).is_some() {
+            write!(f, "/C{}", self.gen)
+        } else {
+            write!(f, "/{}", self.gen)
+        }
+    }
+}
@@ -5,13 +5,18 @@ use crate::{
     cells::{Coord, State},
     config::Config,
     error::Error,
-    rules::{Life, LifeGen, NtLife, NtLifeGen, Rule},
-    search::{Reason, SetCell},
+    rules::{with_rule, Rule, WithRule},
+    search::{Reason, SetCell, Status},
     traits::Search,
     world::World,
 };
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "compressed")]
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+#[cfg(feature = "compressed")]
+use std::io::Read;
+
 /// A representation of `SetCell` which can be easily serialized.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 struct SetCellSer {
@@ -35,15 +40,77 @@ impl<'a, R: Rule> SetCell<'a, R> {
     }
 }
 
+/// The version of the format of `WorldSer`.
+///
+/// It should be increased whenever a change in `WorldSer` or `Config`
+/// cannot be handled by `#[serde(default)]` alone.
+///
+/// The compressed format is positional, so `#[serde(default)]` does not
+/// help there: it should also be increased whenever a field is added to
+/// `WorldSer`, `Config`, or any type in them.
+///
+/// Saves without a version are treated as version `0`.
+///
+/// Version `2` adds the version in front of compressed saves.
+/// Version `3` adds `Reason::LastTry` to the reasons in the `set_stack`.
+/// Version `4` adds `seed` to the configuration.
+/// Version `5` adds `symmetry_offset` to the configuration.
+const SAVE_VERSION: u32 = 5;
+
 /// A representation of the world which can be easily serialized.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// The state of the random number generator is not saved. A restored
+/// world seeds a new one from `seed` of the configuration, so with
+/// `NewState::Random`, it may make different choices from the original.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct WorldSer {
+    /// The version of the format.
+    ///
+    /// See `SAVE_VERSION` for details.
+    #[serde(default)]
+    version: u32,
+
     /// World configuration.
     config: Config,
 
     /// Number of conflicts during the search.
     conflicts: u64,
 
+    /// Number of decisions during the search.
+    #[serde(default)]
+    decisions: u64,
+
+    /// Number of cells examined by `proceed` during the search.
+    #[serde(default)]
+    propagations: u64,
+
+    /// Maximal length of the `set_stack` during the search.
+    #[serde(default)]
+    max_depth: usize,
+
+    /// Maximal decision level during the search.
+    ///
+    /// The current decision level is not saved,
+    /// as it is restored from the `set_stack`.
+    #[serde(default)]
+    max_decision_level: usize,
+
+    /// Number of results found during the search.
+    #[serde(default)]
+    results: u64,
+
+    /// Number of restarts during the search.
+    #[serde(default)]
+    restarts: u64,
+
+    /// Number of decisions that reuse a saved phase.
+    #[serde(default)]
+    saved_phases: u64,
+
+    /// The number of conflicts at which the search will restart.
+    #[serde(default)]
+    next_restart: Option<u64>,
+
     /// A stack to records the cells whose values are set during the search.
     ///
     /// The cells in this table always have known states.
@@ -63,7 +130,7 @@ pub struct WorldSer {
 impl WorldSer {
     /// Restores the world from the `WorldSer`, with the given rule.
     fn world_with_rule<'a, R: Rule>(&self, rule: R) -> Result<World<'a, R>, Error> {
-        let mut world = World::new(&self.config, rule);
+        let mut world = World::new(&self.config, rule)?;
         for &SetCellSer {
             coord,
             state,
@@ -80,6 +147,14 @@ impl WorldSer {
             }
         }
         world.conflicts = self.conflicts;
+        world.decisions = self.decisions;
+        world.propagations = self.propagations;
+        world.max_depth = self.max_depth;
+        world.max_decision_level = world.max_decision_level.max(self.max_decision_level);
+        world.results = self.results;
+        world.restarts = self.restarts;
+        world.saved_phases = self.saved_phases;
+        world.next_restart = self.next_restart;
         world.check_index = self.check_index;
         world.search_index = self.search_index;
         Ok(world)
@@ -87,32 +162,73 @@ impl WorldSer {
 
     /// Restores the world from the `WorldSer`.
     pub fn world(&self) -> Result<Box<dyn Search>, Error> {
-        if let Ok(rule) = self.config.rule_string.parse::<Life>() {
-            let world = self.world_with_rule(rule)?;
-            Ok(Box::new(world))
-        } else if let Ok(rule) = self.config.rule_string.parse::<NtLife>() {
-            let world = self.world_with_rule(rule)?;
-            Ok(Box::new(world))
-        } else if let Ok(rule) = self.config.rule_string.parse::<LifeGen>() {
-            if rule.gen() > 2 {
-                let world = self.world_with_rule(rule)?;
-                Ok(Box::new(world))
-            } else {
-                let rule = rule.non_gen();
-                let world = self.world_with_rule(rule)?;
-                Ok(Box::new(world))
-            }
-        } else {
-            let rule = self.config.rule_string.parse::<NtLifeGen>()?;
-            if rule.gen() > 2 {
-                let world = self.world_with_rule(rule)?;
-                Ok(Box::new(world))
-            } else {
-                let rule = rule.non_gen();
-                let world = self.world_with_rule(rule)?;
-                Ok(Box::new(world))
-            }
+        // In JSON, older versions only differ from the current version in
+        // some missing fields, which are filled in by `#[serde(default)]`.
+        if self.version > SAVE_VERSION {
+            return Err(Error::IncompatibleVersion(self.version));
         }
+        self.config.validate()?;
+        with_rule(&self.config.rule_string, RestoreWorld(self))
+    }
+}
+
+/// Restores the world from a `WorldSer` and a rule of any type.
+/// See `world_with_rule` of `WorldSer`.
+struct RestoreWorld<'w>(&'w WorldSer);
+
+impl WithRule for RestoreWorld<'_> {
+    type Output = Box<dyn Search>;
+
+    fn call<R: Rule>(self, rule: R) -> Result<Self::Output, Error> {
+        Ok(Box::new(self.0.world_with_rule(rule)?))
+    }
+}
+
+#[cfg(feature = "compressed")]
+impl WorldSer {
+    /// Encodes the `WorldSer` in a compact binary format,
+    /// compressed with gzip.
+    ///
+    /// This is much smaller than JSON for large worlds,
+    /// but not human-readable.
+    ///
+    /// The data starts with the version of the format in 4 bytes,
+    /// in little endian, followed by the compressed `WorldSer`.
+    pub fn to_compressed(&self) -> Vec<u8> {
+        let mut data = SAVE_VERSION.to_le_bytes().to_vec();
+        let mut encoder = GzEncoder::new(&mut data, Compression::default());
+        bincode::serialize_into(&mut encoder, self).unwrap();
+        encoder.finish().unwrap();
+        data
+    }
+
+    /// Decodes a `WorldSer` from the output of `to_compressed`.
+    ///
+    /// Unlike JSON saves, only compressed saves of the current version
+    /// can be decoded, because the binary format does not record the
+    /// names of the fields.
+    pub fn from_compressed(data: &[u8]) -> Result<Self, Error> {
+        // Compressed saves of version `1` start with the gzip header.
+        if data.len() < 4 || data.starts_with(&[0x1f, 0x8b]) {
+            return Err(Error::CompressedSaveError(
+                "no version, which is no longer supported".to_owned(),
+            ));
+        }
+        let (version, data) = data.split_at(4);
+        let version = u32::from_le_bytes([version[0], version[1], version[2], version[3]]);
+        if version > SAVE_VERSION {
+            return Err(Error::IncompatibleVersion(version));
+        } else if version < SAVE_VERSION {
+            return Err(Error::CompressedSaveError(format!(
+                "version {} is no longer supported",
+                version
+            )));
+        }
+        let mut bytes = Vec::new();
+        GzDecoder::new(data)
+            .read_to_end(&mut bytes)
+            .map_err(|e| Error::CompressedSaveError(e.to_string()))?;
+        bincode::deserialize(&bytes).map_err(|e| Error::CompressedSaveError(e.to_string()))
     }
 }
 
@@ -120,11 +236,57 @@ impl<'a, R: Rule> World<'a, R> {
     /// Saves the world as a `WorldSer`.
     pub fn ser(&self) -> WorldSer {
         WorldSer {
+            version: SAVE_VERSION,
             config: self.config.clone(),
             conflicts: self.conflicts,
+            decisions: self.decisions,
+            propagations: self.propagations,
+            max_depth: self.max_depth,
+            max_decision_level: self.max_decision_level,
+            results: self.results,
+            restarts: self.restarts,
+            saved_phases: self.saved_phases,
+            next_restart: self.next_restart,
             set_stack: self.set_stack.iter().map(|s| s.ser()).collect(),
             check_index: self.check_index,
             search_index: self.search_index,
         }
     }
 }
+
+/// The result of searching with one rule in `Config::sweep_rules`.
+pub type SweepResult = Result<(Status, Option<WorldSer>), Error>;
+
+impl Config {
+    /// Searches the same configuration with each of the given rules.
+    ///
+    /// A new world is built for each rule, and searched with at most
+    /// `max_step` steps. For each rule, returns the rule string and
+    /// either the status of the search, together with the saved world
+    /// if a result is found, or the error if the world cannot be built,
+    /// e.g., because the rule string is invalid.
+    ///
+    /// An error in one rule does not stop the search with other rules.
+    pub fn sweep_rules(
+        &self,
+        rules: &[String],
+        max_step: Option<u64>,
+    ) -> Vec<(String, SweepResult)> {
+        rules
+            .iter()
+            .map(|rule_string| {
+                let config = self.clone().set_rule_string(rule_string);
+                let result = config.world().map(|mut world| {
+                    let status = world.search(max_step);
+                    let ser = if status == Status::Found {
+                        Some(world.ser())
+                    } else {
+                        None
+                    };
+                    (status, ser)
+                });
+                (rule_string.clone(), result)
+            })
+            .collect()
+    }
+}
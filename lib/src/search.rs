@@ -1,15 +1,47 @@
 //! The search process.
 use crate::{
-    cells::{CellRef, State},
-    config::NewState,
+    cells::{CellRef, Coord, State},
+    config::{DecisionHeuristic, NewState},
+    error::Error,
     rules::Rule,
+    traits::Search,
     world::World,
 };
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::ControlFlow,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "trace")]
+use std::collections::VecDeque;
+
+/// The number of conflicts between two restarts is this number
+/// times a term of the Luby sequence.
+pub(crate) const RESTART_UNIT: u64 = 100;
+
+/// The `i`-th term of the [Luby sequence](https://oeis.org/A182105),
+/// starting from `i = 1`: `1, 1, 2, 1, 1, 2, 4, 1, 1, 2, ...`.
+fn luby(mut i: u64) -> u64 {
+    loop {
+        let k = 64 - i.leading_zeros();
+        if i == (1 << k) - 1 {
+            return 1 << (k - 1);
+        }
+        i -= (1 << (k - 1)) - 1;
+    }
+}
+
+/// The activities of the cells decay by this factor after each conflict.
+const ACTIVITY_DECAY: f64 = 0.95;
+
 /// Search status.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -26,6 +58,124 @@ pub enum Status {
     Paused,
 }
 
+/// Displays the status as a short message, e.g., `Found a result.`.
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let s = match self {
+            Status::Initial => "Waiting to start.",
+            Status::Found => "Found a result.",
+            Status::None => "No more result.",
+            Status::Searching => "Searching...",
+            Status::Paused => "Paused.",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A callback that receives the statistics of the search.
+///
+/// See `set_progress_callback` of `Search`.
+pub type ProgressCallback = Box<dyn FnMut(&Statistics)>;
+
+/// Why the search returns `Status::None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum NoneReason {
+    /// No results were found. Such pattern does not exist.
+    NotExist,
+
+    /// All the results have been found.
+    NoMore,
+
+    /// Some results were found, and `reduce_max` has lowered
+    /// `max_cell_count` below the cell count of the last result.
+    /// No smaller results exist, but there may be other results
+    /// of the same or larger size.
+    NoSmaller,
+}
+
+/// Why a cell has its current state.
+///
+/// Unlike the reasons kept internally by the search,
+/// this does not refer to other cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SetReason {
+    /// Fixed by the configuration, e.g., a cell outside the world,
+    /// or a cell whose symmetric cells are outside the world.
+    Init,
+
+    /// Given in `known_cells` of the configuration.
+    Known,
+
+    /// Chosen by the search.
+    Assume,
+
+    /// Deduced from other cells by the rule or the symmetry.
+    Deduce,
+
+    /// The other states of the cell have led to conflicts.
+    Conflict,
+}
+
+/// A change of the state of a cell, recorded when the `trace` feature
+/// is enabled.
+///
+/// See `set_trace_capacity` of `Search`.
+#[cfg(feature = "trace")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TraceEvent {
+    /// The coordinates of the cell.
+    pub coord: Coord,
+
+    /// The new state of the cell. `None` means that the cell is cleared.
+    pub state: Option<State>,
+
+    /// Why the cell is set. `None` means that the cell is cleared.
+    pub reason: Option<SetReason>,
+
+    /// The decision level after the change.
+    pub level: usize,
+}
+
+/// Statistics of the search.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Statistics {
+    /// Number of cells whose states are decided by choice.
+    pub decisions: u64,
+
+    /// Number of cells examined when deducing the consequences
+    /// of the known cells.
+    pub propagations: u64,
+
+    /// Number of conflicts.
+    pub conflicts: u64,
+
+    /// Maximal number of known cells in the `set_stack`
+    /// at the same time.
+    pub max_depth: usize,
+
+    /// Current decision level, i.e., number of cells that are
+    /// decided by choice at the moment.
+    pub decision_level: usize,
+
+    /// Maximal decision level.
+    ///
+    /// It can be bounded by `max_decision_level` of the configuration.
+    pub max_decision_level: usize,
+
+    /// Number of results found.
+    pub results: u64,
+
+    /// Number of restarts.
+    pub restarts: u64,
+
+    /// Number of decisions that reuse a saved phase.
+    pub saved_phases: u64,
+}
+
 /// Reasons for setting a cell.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
@@ -43,6 +193,36 @@ pub(crate) enum Reason {
     /// Remembers its position in the `search_list` of the world,
     /// and the number of remaining states to try.
     TryAnother(usize, usize),
+
+    /// Tries the last state of a cell, after all other states
+    /// have led to conflicts.
+    ///
+    /// Unlike `TryAnother`, this does not count in the decision level,
+    /// and is backtracked in the same way as `Deduce`.
+    LastTry,
+
+    /// The state of a cell is given in `known_cells` of the configuration.
+    ///
+    /// The backtracking never goes beyond such cells.
+    Known,
+}
+
+impl Reason {
+    /// Whether the cell is decided by choice,
+    /// i.e., whether it counts in the decision level.
+    pub(crate) fn is_decision(self) -> bool {
+        matches!(self, Reason::Decide(_) | Reason::TryAnother(_, _))
+    }
+
+    /// The corresponding public reason, without references to other cells.
+    pub(crate) fn set_reason(self) -> SetReason {
+        match self {
+            Reason::Known => SetReason::Known,
+            Reason::Decide(_) => SetReason::Assume,
+            Reason::Deduce => SetReason::Deduce,
+            Reason::TryAnother(_, _) | Reason::LastTry => SetReason::Conflict,
+        }
+    }
 }
 
 /// Records the cells whose values are set and their reasons.
@@ -78,15 +258,17 @@ impl<'a, R: Rule> World<'a, R> {
 
     /// Consistifies a cell, its neighbors, and its predecessor.
     ///
+    /// For still lives (period 1 without translation or transformation),
+    /// the predecessor is the cell itself, so it is skipped.
+    ///
     /// Returns `false` if there is a conflict,
     /// `true` if the cells are consistent.
     fn consistify10(&mut self, cell: CellRef<'a, R>) -> bool {
         self.consistify(cell)
             && {
-                if let Some(pred) = cell.pred {
-                    self.consistify(pred)
-                } else {
-                    true
+                match cell.pred {
+                    Some(pred) if pred != cell => self.consistify(pred),
+                    _ => true,
                 }
             }
             && cell
@@ -95,11 +277,36 @@ impl<'a, R: Rule> World<'a, R> {
                 .all(|&neigh| self.consistify(neigh.unwrap()))
     }
 
+    /// Checks the `forbidden` pattern.
+    ///
+    /// If all but one cells in the pattern have the forbidden states,
+    /// and the remaining one is unknown, sets it to the other state.
+    /// This only works for non-Generations rules.
+    ///
+    /// Returns `false` if all cells have the forbidden states,
+    /// `true` otherwise.
+    pub(crate) fn check_forbidden(&mut self) -> bool {
+        let mut unknown = None;
+        for &(cell, state) in self.forbidden.iter() {
+            match cell.state.get() {
+                Some(old_state) if old_state == state => (),
+                Some(_) => return true,
+                None if unknown.is_none() => unknown = Some((cell, state)),
+                None => return true,
+            }
+        }
+        match unknown {
+            Some((cell, state)) if !R::IS_GEN => self.set_cell(cell, !state, Reason::Deduce),
+            Some(_) => true,
+            None => self.forbidden.is_empty(),
+        }
+    }
+
     /// Deduces all the consequences by `consistify` and symmetry.
     ///
     /// Returns `false` if there is a conflict,
     /// `true` if the cells are consistent.
-    fn proceed(&mut self) -> bool {
+    pub(crate) fn proceed(&mut self) -> bool {
         while self.check_index < self.set_stack.len() {
             let cell = self.set_stack[self.check_index].cell;
             let state = cell.state.get().unwrap();
@@ -120,7 +327,18 @@ impl<'a, R: Rule> World<'a, R> {
                 return false;
             }
 
+            // Excludes the forbidden pattern.
+            if self
+                .forbidden
+                .iter()
+                .any(|&(forbidden, _)| forbidden == cell)
+                && !self.check_forbidden()
+            {
+                return false;
+            }
+
             self.check_index += 1;
+            self.propagations += 1;
         }
         true
     }
@@ -133,6 +351,9 @@ impl<'a, R: Rule> World<'a, R> {
     fn backup(&mut self) -> bool {
         while let Some(set_cell) = self.set_stack.pop() {
             let cell = set_cell.cell;
+            if set_cell.reason.is_decision() {
+                self.decision_level -= 1;
+            }
             match set_cell.reason {
                 Reason::Decide(i) => {
                     self.check_index = self.set_stack.len();
@@ -147,7 +368,7 @@ impl<'a, R: Rule> World<'a, R> {
                     } else {
                         let state = !cell.state.get().unwrap();
                         self.clear_cell(cell);
-                        if self.set_cell(cell, state, Reason::Deduce) {
+                        if self.set_cell(cell, state, Reason::LastTry) {
                             return true;
                         }
                     }
@@ -159,7 +380,7 @@ impl<'a, R: Rule> World<'a, R> {
                     let state = State((j + 1) % self.rule.gen());
                     self.clear_cell(cell);
                     let reason = if n == 1 {
-                        Reason::Deduce
+                        Reason::LastTry
                     } else {
                         Reason::TryAnother(i, n - 1)
                     };
@@ -167,9 +388,14 @@ impl<'a, R: Rule> World<'a, R> {
                         return true;
                     }
                 }
-                Reason::Deduce => {
+                Reason::Deduce | Reason::LastTry => {
+                    cell.phase.set(cell.state.get());
                     self.clear_cell(cell);
                 }
+                Reason::Known => {
+                    self.set_stack.push(set_cell);
+                    break;
+                }
             }
         }
         self.check_index = 0;
@@ -177,6 +403,71 @@ impl<'a, R: Rule> World<'a, R> {
         false
     }
 
+    /// Increases the activities of the cells involved in a conflict
+    /// when consistifying `cell`, i.e., the cell itself, its neighbors,
+    /// and its predecessor.
+    ///
+    /// Used by `DecisionHeuristic::Vsids`.
+    fn bump_activity(&mut self, cell: CellRef<'a, R>) {
+        let cells = Some(cell)
+            .into_iter()
+            .chain(cell.pred)
+            .chain(cell.nbhd.iter().copied().flatten());
+        for cell in cells {
+            cell.activity.set(cell.activity.get() + self.activity_inc);
+            if let Some(pos) = cell.search_pos {
+                self.activity_heap.increase(pos, &self.search_list);
+            }
+        }
+        self.activity_inc /= ACTIVITY_DECAY;
+
+        // Rescales all the activities to avoid overflow.
+        if self.activity_inc > 1e100 {
+            for cell in self.cells.iter() {
+                cell.activity.set(cell.activity.get() * 1e-100);
+            }
+            self.activity_inc *= 1e-100;
+        }
+    }
+
+    /// Restarts the search if the number of conflicts reaches
+    /// `next_restart`.
+    ///
+    /// Backtracks to the first decision, keeping all the cells that are
+    /// set before it, and sets the threshold for the next restart.
+    ///
+    /// Returns `true` if it restarts.
+    fn restart(&mut self) -> bool {
+        match self.next_restart {
+            Some(next) if self.conflicts >= next => (),
+            _ => return false,
+        }
+        self.clear_decisions();
+        self.restarts += 1;
+        self.next_restart = Some(self.conflicts + RESTART_UNIT * luby(self.restarts + 1));
+        true
+    }
+
+    /// Backtracks to the time before the first decision,
+    /// saving the states of the cleared cells as their phases.
+    ///
+    /// Cells deduced before the first decision are kept.
+    fn clear_decisions(&mut self) {
+        let first_decision = self
+            .set_stack
+            .iter()
+            .position(|set_cell| set_cell.reason.is_decision())
+            .unwrap_or(self.set_stack.len());
+        while self.set_stack.len() > first_decision {
+            let cell = self.set_stack.pop().unwrap().cell;
+            cell.phase.set(cell.state.get());
+            self.clear_cell(cell);
+        }
+        self.decision_level = 0;
+        self.check_index = self.set_stack.len();
+        self.search_index = 0;
+    }
+
     /// Keeps proceeding and backtracking,
     /// until there are no more cells to examine (and returns `true`),
     /// or the backtracking goes back to the time before the first cell is set
@@ -191,9 +482,20 @@ impl<'a, R: Rule> World<'a, R> {
         loop {
             *step += 1;
             if self.proceed() {
+                self.max_depth = self.max_depth.max(self.set_stack.len());
                 return true;
             } else {
                 self.conflicts += 1;
+                if let Some((interval, _)) = self.progress {
+                    if self.conflicts.is_multiple_of(interval) {
+                        let statistics = self.statistics();
+                        (self.progress.as_mut().unwrap().1)(&statistics);
+                    }
+                }
+                if self.config.decision_heuristic == DecisionHeuristic::Vsids {
+                    let cell = self.set_stack[self.check_index].cell;
+                    self.bump_activity(cell);
+                }
                 if !self.backup() {
                     return false;
                 }
@@ -207,14 +509,36 @@ impl<'a, R: Rule> World<'a, R> {
     /// and push a reference to it to the `set_stack`.
     ///
     /// Returns `None` is there is no unknown cell,
-    /// `Some(false)` if the new state leads to an immediate conflict.
+    /// `Some(false)` if the new state leads to an immediate conflict,
+    /// or if the decision level would exceed `max_decision_level`.
     fn decide(&mut self) -> Option<bool> {
-        if let Some((i, cell)) = self.get_unknown(self.search_index) {
+        let unknown = match self.config.decision_heuristic {
+            DecisionHeuristic::Positional => self.get_unknown(self.search_index),
+            DecisionHeuristic::Vsids => self.get_most_active(),
+        };
+        if let Some((i, cell)) = unknown {
+            if matches!(self.config.max_decision_level, Some(max) if self.decision_level >= max) {
+                return Some(false);
+            }
             self.search_index = i + 1;
-            let state = match self.config.new_state {
-                NewState::ChooseDead => cell.background,
-                NewState::ChooseAlive => !cell.background,
-                NewState::Random => State(thread_rng().gen_range(0, self.rule.gen())),
+            self.decisions += 1;
+            let state = match cell.phase.get() {
+                Some(state) if self.config.phase_saving => {
+                    self.saved_phases += 1;
+                    state
+                }
+                _ => match self.config.new_state {
+                    NewState::ChooseDead => cell.background,
+                    NewState::ChooseAlive => !cell.background,
+                    NewState::Random => State(self.rng.gen_range(0, self.rule.gen())),
+                    NewState::RandomBiased(p) => {
+                        if self.rng.gen_bool(p) {
+                            !cell.background
+                        } else {
+                            cell.background
+                        }
+                    }
+                },
             };
             Some(self.set_cell(cell, state, Reason::Decide(i)))
         } else {
@@ -230,17 +554,36 @@ impl<'a, R: Rule> World<'a, R> {
     /// and no results are found.
     pub fn search(&mut self, max_step: Option<u64>) -> Status {
         let mut step_count = 0;
-        if self.get_unknown(0).is_none() && !self.backup() {
-            return Status::None;
+        self.search_steps(max_step, &mut step_count)
+    }
+
+    /// The same as `search`, but counts the steps in `step_count`,
+    /// which may be shared by several searches.
+    fn search_steps(&mut self, max_step: Option<u64>, step_count: &mut u64) -> Status {
+        if self.get_unknown(0).is_none() {
+            // With `reduce_max`, no result is smaller than an empty one.
+            if self.config.reduce_max && self.limited_cell_count() == 0 {
+                return Status::None;
+            }
+            if !self.backup() {
+                return Status::None;
+            }
         }
-        while self.go(&mut step_count) {
-            if let Some(result) = self.decide() {
+        while self.go(step_count) {
+            if self.restart() {
+                // A restart takes the place of a decision. The step limit
+                // and the abort flag below are still checked.
+            } else if let Some(result) = self.decide() {
                 if !result && !self.backup() {
                     return Status::None;
                 }
             } else if self.nontrivial() {
+                self.results += 1;
+                self.next_restart = None;
                 if self.config.reduce_max {
-                    self.config.max_cell_count = Some(self.cell_count() - 1);
+                    if let Some(max) = self.limited_cell_count().checked_sub(1) {
+                        self.config.max_cell_count = Some(max);
+                    }
                 }
                 return Status::Found;
             } else if !self.backup() {
@@ -248,23 +591,303 @@ impl<'a, R: Rule> World<'a, R> {
             }
 
             if let Some(max) = max_step {
-                if step_count > max {
+                if *step_count > max {
                     return Status::Searching;
                 }
             }
+            if self.abort.swap(false, Ordering::Relaxed) {
+                return Status::Paused;
+            }
         }
         Status::None
     }
 
+    /// Searches for all the results, and calls `on_found` with each result
+    /// as soon as it is found.
+    ///
+    /// If `on_found` returns `ControlFlow::Continue`, the search goes on
+    /// to look for the next result. If it returns `ControlFlow::Break`,
+    /// the search stops and returns `Found`. The search can be continued
+    /// later by calling `search` or `search_with_callback` again.
+    ///
+    /// `max_step` limits the total number of steps of all the results.
+    /// Returns `None` if there are no more results,
+    /// `Searching` if the number of steps exceeds `max_step`,
+    /// `Paused` if the flag returned by `abort_handle` is set.
+    ///
+    /// `on_found` only gets a shared reference to the world,
+    /// which is valid until it returns. The cells of the world are
+    /// changed by the search through interior mutability,
+    /// so anything that is needed later, e.g., an RLE or a `WorldSer`,
+    /// should be copied out in `on_found`.
+    pub fn search_with_callback(
+        &mut self,
+        max_step: Option<u64>,
+        on_found: &mut dyn FnMut(&dyn Search) -> ControlFlow<()>,
+    ) -> Status {
+        let mut step_count = 0;
+        loop {
+            match self.search_steps(max_step, &mut step_count) {
+                Status::Found => {
+                    if let ControlFlow::Break(()) = on_found(self) {
+                        return Status::Found;
+                    }
+                }
+                status => return status,
+            }
+        }
+    }
+
+    /// Statistics of the search.
+    pub fn statistics(&self) -> Statistics {
+        Statistics {
+            decisions: self.decisions,
+            propagations: self.propagations,
+            conflicts: self.conflicts,
+            max_depth: self.max_depth,
+            decision_level: self.decision_level,
+            max_decision_level: self.max_decision_level,
+            results: self.results,
+            restarts: self.restarts,
+            saved_phases: self.saved_phases,
+        }
+    }
+
+    /// Why the search returns `Status::None`.
+    ///
+    /// Only meaningful after `search` returns `Status::None`.
+    pub fn none_reason(&self) -> NoneReason {
+        if self.results == 0 {
+            NoneReason::NotExist
+        } else if self.config.reduce_max {
+            NoneReason::NoSmaller
+        } else {
+            NoneReason::NoMore
+        }
+    }
+
+    /// An estimate of the fraction of the search space that has been
+    /// explored, between `0` and `1`.
+    ///
+    /// The search space is seen as a tree, where each decision splits
+    /// the current branch into equal parts, one for each state.
+    /// The estimate is the total size of the branches that have been
+    /// completely explored.
+    ///
+    /// This is only a heuristic: the actual branches have very different
+    /// sizes, so the estimate may jump when the search backtracks.
+    /// It goes back to `0` when the search returns `Status::None`.
+    pub fn progress(&self) -> f64 {
+        let gen = self.rule.gen();
+        let mut size = 1.0;
+        let mut progress = 0.0;
+        for set_cell in self.set_stack.iter() {
+            let branch = match set_cell.reason {
+                Reason::Decide(_) => 0,
+                Reason::TryAnother(_, n) => gen - 1 - n,
+                Reason::LastTry => gen - 1,
+                Reason::Deduce | Reason::Known => continue,
+            };
+            size /= gen as f64;
+            progress += size * branch as f64;
+        }
+        progress
+    }
+
+    /// A flag that stops the search when it is set to `true`,
+    /// e.g., from another thread.
+    ///
+    /// The search checks the flag once per step, and returns
+    /// `Status::Paused` if it is set. The flag is cleared at the same time,
+    /// so the search can be resumed by calling `search` again.
+    pub fn abort_handle(&self) -> Arc<AtomicBool> {
+        self.abort.clone()
+    }
+
+    /// Why a cell has its current state.
+    ///
+    /// Returns `Ok(None)` if the cell is unknown,
+    /// and an error if there is no such cell.
+    pub fn cell_reason(&self, coord: Coord) -> Result<Option<SetReason>, Error> {
+        let cell = self
+            .find_cell(self.config.translate(coord))
+            .ok_or(Error::GetCellError(coord))?;
+        if cell.state.get().is_none() {
+            return Ok(None);
+        }
+        if self.is_fixed(cell) {
+            return Ok(Some(SetReason::Init));
+        }
+        let reason = self
+            .set_stack
+            .iter()
+            .find(|set_cell| set_cell.cell == cell)
+            .map_or(SetReason::Init, |set_cell| set_cell.reason.set_reason());
+        Ok(Some(reason))
+    }
+
+    /// Starts recording the changes of the states of the cells,
+    /// keeping only the last `capacity` changes.
+    ///
+    /// `0` stops the recording. The recorded changes are cleared.
+    #[cfg(feature = "trace")]
+    pub fn set_trace_capacity(&mut self, capacity: usize) {
+        self.trace_capacity = capacity;
+        self.trace = VecDeque::new();
+    }
+
+    /// The recorded changes of the states of the cells, oldest first.
+    ///
+    /// Unlike the `set_stack`, this also records the cells cleared
+    /// by backtracking.
+    #[cfg(feature = "trace")]
+    pub fn trace(&self) -> Vec<TraceEvent> {
+        self.trace.iter().copied().collect()
+    }
+
+    /// Records a change of the state of a cell.
+    ///
+    /// The recorded changes are kept in a ring buffer, which only grows
+    /// as changes are recorded, and the oldest change is dropped when
+    /// it is full.
+    #[cfg(feature = "trace")]
+    pub(crate) fn record(&mut self, cell: CellRef<'a, R>, reason: Option<Reason>) {
+        if self.trace_capacity == 0 {
+            return;
+        }
+        if self.trace.len() >= self.trace_capacity {
+            self.trace.pop_front();
+        }
+        self.trace.push_back(TraceEvent {
+            coord: cell.coord,
+            state: cell.state.get(),
+            reason: reason.map(Reason::set_reason),
+            level: self.decision_level,
+        });
+    }
+
+    /// Sets a callback that is called with the statistics of the search
+    /// every `interval` conflicts.
+    pub fn set_progress_callback(&mut self, interval: u64, callback: ProgressCallback) {
+        self.progress = Some((interval.max(1), callback));
+    }
+
     /// Set the max cell counts.
+    ///
+    /// Lowering the limit keeps the current position of the search,
+    /// and backtracks until the cell count is within the new limit.
+    /// Raising the limit does not revisit the branches that were
+    /// already pruned by the old limit. Use `reset_max_cell_count`
+    /// for that.
     pub(crate) fn set_max_cell_count(&mut self, max_cell_count: Option<usize>) {
         self.config.max_cell_count = max_cell_count;
+        self.init_config.max_cell_count = max_cell_count;
         if let Some(max) = self.config.max_cell_count {
-            while self.cell_count() > max {
+            while self.limited_cell_count() > max {
                 if !self.backup() {
                     break;
                 }
             }
         }
     }
+
+    /// Sets the state of a cell as if it were given in `known_cells`,
+    /// and deduces its consequences.
+    ///
+    /// The search is backtracked to the time before the first decision,
+    /// so that the new cell is never cleared by backtracking.
+    ///
+    /// Returns an error if there is no such cell, if the state is invalid,
+    /// or if the new cell contradicts the cells that are already known.
+    /// In the last case the world is left as it was after backtracking.
+    pub(crate) fn try_set_cell(&mut self, coord: Coord, state: State) -> Result<(), Error> {
+        let cell = self
+            .find_cell(self.config.translate(coord))
+            .ok_or(Error::SetCellError(coord))?;
+        if state.0 >= self.rule.gen() {
+            return Err(Error::SetCellError(coord));
+        }
+        self.clear_decisions();
+        match cell.state.get() {
+            Some(old_state) if old_state != state => return Err(Error::KnownCellsError),
+            Some(_) => (),
+            None => {
+                let len = self.set_stack.len();
+                if !(self.set_cell(cell, state, Reason::Known)
+                    && self.check_forbidden()
+                    && self.proceed())
+                {
+                    while self.set_stack.len() > len {
+                        let cell = self.set_stack.pop().unwrap().cell;
+                        self.clear_cell(cell);
+                    }
+                    self.check_index = len;
+                    return Err(Error::KnownCellsError);
+                }
+            }
+        }
+        if !self.config.known_cells.contains(&(coord, state)) {
+            self.config.known_cells.push((coord, state));
+        }
+        if !self.init_config.known_cells.contains(&(coord, state)) {
+            self.init_config.known_cells.push((coord, state));
+        }
+        Ok(())
+    }
+
+    /// Set the max cell counts, and restarts the search from the beginning
+    /// if the new limit is higher than the old one.
+    ///
+    /// Otherwise it is the same as `set_max_cell_count`.
+    ///
+    /// When restarting, all the cells except the known cells and the cells
+    /// fixed by the configuration are cleared. The statistics and the saved
+    /// phases are kept. Results that were already found will be found again.
+    ///
+    /// Returns an error if setting the fixed cells or the known cells again
+    /// leads to a conflict.
+    pub(crate) fn reset_max_cell_count(
+        &mut self,
+        max_cell_count: Option<usize>,
+    ) -> Result<(), Error> {
+        let raised = match (self.config.max_cell_count, max_cell_count) {
+            (Some(old), Some(new)) => new > old,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        self.config.max_cell_count = max_cell_count;
+        self.init_config.max_cell_count = max_cell_count;
+        if !raised {
+            self.set_max_cell_count(max_cell_count);
+            return Ok(());
+        }
+
+        let mut known = Vec::new();
+        while let Some(set_cell) = self.set_stack.pop() {
+            let cell = set_cell.cell;
+            if let Reason::Known = set_cell.reason {
+                known.push((cell, cell.state.get().unwrap()));
+            } else {
+                cell.phase.set(cell.state.get());
+            }
+            self.clear_cell(cell);
+        }
+        self.decision_level = 0;
+        self.check_index = 0;
+        self.search_index = 0;
+        self.next_restart = if self.config.restart {
+            Some(RESTART_UNIT)
+        } else {
+            None
+        };
+        if !self.set_fixed() {
+            return Err(Error::KnownCellsError);
+        }
+        for (cell, state) in known.into_iter().rev() {
+            if !self.set_cell(cell, state, Reason::Known) {
+                return Err(Error::KnownCellsError);
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,16 +1,291 @@
 //! A trait for `World`.
 use crate::{
-    cells::{Coord, State, ALIVE, DEAD},
-    config::Config,
+    cells::{CellState, Coord, State},
+    config::{Config, SearchOrder},
     error::Error,
     rules::Rule,
-    search::Status,
+    search::{NoneReason, ProgressCallback, SetReason, Statistics, Status},
     world::World,
 };
-use std::fmt::Write;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    io,
+    ops::ControlFlow,
+    sync::{atomic::AtomicBool, Arc},
+    time::{Duration, Instant},
+};
 
+#[cfg(feature = "trace")]
+use crate::search::TraceEvent;
+#[cfg(feature = "serialize")]
+use crate::{save::WorldSer, world::ISOMETRIES};
 #[cfg(feature = "serialize")]
-use crate::save::WorldSer;
+use std::collections::HashSet;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Formats to export a generation of the world.
+///
+/// See `export` of `Search`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum OutputFormat {
+    /// [RLE](https://conwaylife.com/wiki/Rle). See `rle`.
+    Rle,
+    /// RLE trimmed to the living cells. See `rle_trimmed`.
+    RleTrimmed,
+    /// [Plaintext](https://conwaylife.com/wiki/Plaintext). See `to_plaintext`.
+    Plaintext,
+    /// Golly's [macrocell](https://golly.sourceforge.net/Help/formats.html#mc).
+    /// See `to_macrocell`.
+    Macrocell,
+}
+
+/// Maximal length of a line in an RLE file.
+const RLE_LINE_LEN: usize = 70;
+
+/// An item in an RLE file, i.e., a run of identical cells or line ends.
+fn rle_item(count: usize, c: char) -> String {
+    if count == 1 {
+        c.to_string()
+    } else {
+        format!("{}{}", count, c)
+    }
+}
+
+/// Writes the cells of an RLE file, given the character for each cell,
+/// followed by the final `!`.
+///
+/// `dead` is the character for dead cells,
+/// which are omitted at the end of each row.
+fn write_rle_cells(
+    w: &mut dyn io::Write,
+    width: isize,
+    height: isize,
+    dead: char,
+    mut cell: impl FnMut(isize, isize) -> char,
+) -> io::Result<()> {
+    let mut line_len = 0;
+    let mut new_lines = 0;
+    for y in 0..height {
+        let mut runs: Vec<(usize, char)> = Vec::new();
+        for x in 0..width {
+            let c = cell(x, y);
+            match runs.last_mut() {
+                Some((n, last)) if *last == c => *n += 1,
+                _ => runs.push((1, c)),
+            }
+        }
+        if let Some(&(_, c)) = runs.last() {
+            if c == dead {
+                runs.pop();
+            }
+        }
+        if !runs.is_empty() {
+            if new_lines > 0 {
+                write_rle_item(w, &mut line_len, &rle_item(new_lines, '$'))?;
+                new_lines = 0;
+            }
+            for (n, c) in runs {
+                write_rle_item(w, &mut line_len, &rle_item(n, c))?;
+            }
+        }
+        new_lines += 1;
+    }
+    write_rle_item(w, &mut line_len, "!")?;
+    writeln!(w)
+}
+
+/// Writes the cells of some generation in a Plaintext-like format,
+/// with `alive` for living and dying cells, to `str`.
+///
+/// Shared by `plaintext_gen` and `to_plaintext` of `Search`.
+fn write_plaintext<S: Search + ?Sized>(search: &S, t: isize, alive: char, str: &mut String) {
+    for y in 0..search.config().height {
+        for x in 0..search.config().width {
+            match search.cell_state((x, y, t)).unwrap() {
+                CellState::Dead => str.push('.'),
+                CellState::Alive | CellState::Dying(_) => str.push(alive),
+                CellState::Unknown => str.push('?'),
+            };
+        }
+        str.push('\n');
+    }
+}
+
+/// Displays the states of the cells, row by row, in the format of
+/// `rle_gen` of `Search`.
+fn rle_gen_cells<S: Search + ?Sized>(search: &S, cells: &[Vec<CellState>]) -> String {
+    let mut str = String::new();
+    writeln!(
+        str,
+        "x = {}, y = {}, rule = {}",
+        search.config().width,
+        search.config().height,
+        search.config().rule_string
+    )
+    .unwrap();
+    for (y, row) in cells.iter().enumerate() {
+        for &state in row {
+            match state {
+                CellState::Dead => str.push('.'),
+                CellState::Alive => {
+                    if search.is_gen_rule() {
+                        str.push('A')
+                    } else {
+                        str.push('o')
+                    }
+                }
+                CellState::Dying(i) => str.push((b'A' + i as u8 - 1) as char),
+                CellState::Unknown => str.push('?'),
+            };
+        }
+        if y + 1 == cells.len() {
+            str.push('!')
+        } else {
+            str.push('$')
+        };
+        str.push('\n');
+    }
+    str
+}
+
+/// Writes the rectangle `(x, y, width, height)` of the world
+/// in some generation in RLE format.
+///
+/// See `write_rle_with` of `Search` for `relative`.
+fn write_rle_rect<S: Search + ?Sized>(
+    search: &S,
+    t: isize,
+    relative: bool,
+    (x0, y0, width, height): (isize, isize, isize, isize),
+    w: &mut dyn io::Write,
+) -> io::Result<()> {
+    writeln!(
+        w,
+        "x = {}, y = {}, rule = {}",
+        width,
+        height,
+        search.config().rule_string
+    )?;
+    let dead = if search.is_gen_rule() { '.' } else { 'b' };
+    write_rle_cells(w, width, height, dead, |x, y| {
+        let coord = (x0 + x, y0 + y, t);
+        let state = if relative {
+            search.cell_state_relative(coord)
+        } else {
+            search.cell_state(coord)
+        };
+        match state.unwrap() {
+            CellState::Dead | CellState::Unknown => dead,
+            CellState::Alive => {
+                if search.is_gen_rule() {
+                    'A'
+                } else {
+                    'o'
+                }
+            }
+            CellState::Dying(i) => (b'A' + i as u8 - 1) as char,
+        }
+    })
+}
+
+/// Writes an RLE item, breaking the line if it would become too long.
+fn write_rle_item(w: &mut dyn io::Write, line_len: &mut usize, item: &str) -> io::Result<()> {
+    if *line_len + item.len() > RLE_LINE_LEN {
+        writeln!(w)?;
+        *line_len = 0;
+    }
+    *line_len += item.len();
+    w.write_all(item.as_bytes())
+}
+
+/// The nodes of a [macrocell](https://golly.sourceforge.net/Help/formats.html#mc)
+/// file, built from the states of the cells.
+struct Macrocell<'a> {
+    /// The states of the cells, in rows.
+    ///
+    /// `0` is dead, `1` is alive, and larger numbers are dying states.
+    cells: &'a [Vec<usize>],
+    /// Whether the rule has more than 2 states.
+    is_gen: bool,
+    /// The lines of the nodes. Node `i` is on line `i - 1`.
+    lines: Vec<String>,
+    /// The index of each node that has been written,
+    /// so that identical nodes are only written once.
+    indices: HashMap<String, usize>,
+}
+
+impl<'a> Macrocell<'a> {
+    /// The state of the cell at `(x, y)`. Cells outside are dead.
+    fn state(&self, x: usize, y: usize) -> usize {
+        self.cells
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Writes the node of the given level whose upper left corner is
+    /// `(x, y)`, and returns its index. `0` means an empty node.
+    ///
+    /// For rules with 2 states, the leaves are 8x8 blocks of level 3.
+    /// For rules with more states, the leaves are 2x2 blocks of level 1.
+    fn node(&mut self, x: usize, y: usize, level: u32) -> usize {
+        let line = if !self.is_gen && level == 3 {
+            let mut line = String::new();
+            for dy in 0..8 {
+                let row: String = (0..8)
+                    .map(|dx| {
+                        if self.state(x + dx, y + dy) == 0 {
+                            '.'
+                        } else {
+                            '*'
+                        }
+                    })
+                    .collect();
+                line.push_str(row.trim_end_matches('.'));
+                line.push('$');
+            }
+            let line = line.trim_end_matches('$');
+            if line.is_empty() {
+                return 0;
+            }
+            format!("{}$", line)
+        } else {
+            let half = 1 << (level - 1);
+            let children = if level == 1 {
+                [
+                    self.state(x, y),
+                    self.state(x + 1, y),
+                    self.state(x, y + 1),
+                    self.state(x + 1, y + 1),
+                ]
+            } else {
+                [
+                    self.node(x, y, level - 1),
+                    self.node(x + half, y, level - 1),
+                    self.node(x, y + half, level - 1),
+                    self.node(x + half, y + half, level - 1),
+                ]
+            };
+            if children == [0; 4] {
+                return 0;
+            }
+            let [nw, ne, sw, se] = children;
+            format!("{} {} {} {} {}", level, nw, ne, sw, se)
+        };
+        if let Some(&index) = self.indices.get(&line) {
+            return index;
+        }
+        self.lines.push(line.clone());
+        self.indices.insert(line, self.lines.len());
+        self.lines.len()
+    }
+}
 
 /// A trait for `World`.
 ///
@@ -21,15 +296,128 @@ pub trait Search {
     /// Returns `Found` if a result is found,
     /// `None` if such pattern does not exist,
     /// `Searching` if the number of steps exceeds `max_step`
-    /// and no results are found.
+    /// and no results are found,
+    /// `Paused` if the flag returned by `abort_handle` is set.
     fn search(&mut self, max_step: Option<u64>) -> Status;
 
+    /// Searches until a result is found, or the `deadline` passes.
+    ///
+    /// The clock is only checked every `step_interval` steps,
+    /// so that checking it does not dominate the running time
+    /// on small worlds. Thus the search may run a little longer
+    /// than the deadline.
+    ///
+    /// Returns `Found` if a result is found,
+    /// `None` if such pattern does not exist,
+    /// `Searching` if the deadline passes and no results are found.
+    ///
+    /// Note that `std::time::Instant` is not available on
+    /// `wasm32-unknown-unknown`.
+    fn search_until(&mut self, deadline: Instant, step_interval: u64) -> Status {
+        loop {
+            match self.search(Some(step_interval)) {
+                Status::Searching if Instant::now() < deadline => continue,
+                status => return status,
+            }
+        }
+    }
+
+    /// The same as `search`, but also returns the statistics after
+    /// the search, and the wall-clock time that the search takes,
+    /// e.g., for comparing configurations in benchmarks.
+    ///
+    /// Only the call to `search` is timed, not the construction of
+    /// the world. The statistics are accumulated since the world
+    /// was created, not only in this call.
+    ///
+    /// Note that `std::time::Instant` is not available on
+    /// `wasm32-unknown-unknown`.
+    fn search_timed(&mut self, max_step: Option<u64>) -> (Status, Statistics, Duration) {
+        let start = Instant::now();
+        let status = self.search(max_step);
+        let elapsed = start.elapsed();
+        (status, self.statistics(), elapsed)
+    }
+
+    /// Searches for all the results, and calls `on_found` with each result
+    /// as soon as it is found, instead of collecting them.
+    ///
+    /// If `on_found` returns `ControlFlow::Continue`, the search goes on
+    /// to look for the next result. If it returns `ControlFlow::Break`,
+    /// the search stops and returns `Found`.
+    ///
+    /// `max_step` limits the total number of steps of all the results.
+    /// Returns `None` if there are no more results,
+    /// `Searching` if the number of steps exceeds `max_step`,
+    /// `Paused` if the flag returned by `abort_handle` is set.
+    ///
+    /// `on_found` only gets a shared reference to the world, which is
+    /// valid until it returns, and cannot start another search on it.
+    /// The cells of the world are changed by the search through interior
+    /// mutability, so anything that is needed later, e.g., an RLE or
+    /// a `WorldSer`, should be copied out in `on_found`.
+    fn search_with_callback(
+        &mut self,
+        max_step: Option<u64>,
+        on_found: &mut dyn FnMut(&dyn Search) -> ControlFlow<()>,
+    ) -> Status;
+
+    /// Continues the search, e.g., after the world is restored
+    /// by `WorldSer::world`.
+    ///
+    /// This is the same as `search`: the search always continues from
+    /// the current state of the world. If the last search found
+    /// a result, it looks for the next one.
+    fn resume(&mut self, max_step: Option<u64>) -> Status {
+        self.search(max_step)
+    }
+
     /// Gets the state of a cell. Returns `Err(())` if there is no such cell.
     fn get_cell_state(&self, coord: Coord) -> Result<Option<State>, Error>;
 
+    /// Gets the state of a cell. Returns `None` if the cell is unknown
+    /// or if there is no such cell.
+    fn get_cell(&self, coord: Coord) -> Option<State> {
+        self.get_cell_state(coord).ok().flatten()
+    }
+
+    /// Sets the state of a cell as if it were given in `known_cells`
+    /// of the configuration, and deduces its consequences.
+    ///
+    /// This backtracks the search to the time before the first decision,
+    /// so that searching again will respect the new cell.
+    ///
+    /// Returns an error if there is no such cell, if the state is invalid,
+    /// or if the new cell contradicts the cells that are already known.
+    /// The search is backtracked even if the cell is rejected.
+    fn try_set_cell(&mut self, coord: Coord, state: State) -> Result<(), Error>;
+
+    /// Gets the state of a cell as a `CellState`, so that unknown cells
+    /// are `CellState::Unknown` instead of `None`.
+    /// Returns an error if there is no such cell.
+    fn cell_state(&self, coord: Coord) -> Result<CellState, Error> {
+        self.get_cell_state(coord).map(CellState::from)
+    }
+
     /// World configuration.
     fn config(&self) -> &Config;
 
+    /// The search order used by the search.
+    ///
+    /// A shortcut for `config().auto_search_order()`,
+    /// which also resolves the automatic search order.
+    fn search_order(&self) -> SearchOrder {
+        self.config().auto_search_order()
+    }
+
+    /// The width, height and period of the world.
+    ///
+    /// A shortcut for the corresponding fields of `config`.
+    fn dimensions(&self) -> (isize, isize, isize) {
+        let config = self.config();
+        (config.width, config.height, config.period)
+    }
+
     /// Whether the rule is a Generations rule.
     fn is_gen_rule(&self) -> bool;
 
@@ -41,6 +429,10 @@ pub trait Search {
 
     /// Number of known living cells in some generation.
     ///
+    /// `t` can be any generation, not only those in `0..period`:
+    /// the pattern repeats itself after a period, up to translations
+    /// and transformations, which do not change the cell count.
+    ///
     /// For Generations rules, dying cells are not counted.
     fn cell_count_gen(&self, t: isize) -> usize;
 
@@ -49,20 +441,216 @@ pub trait Search {
     /// For Generations rules, dying cells are not counted.
     fn cell_count(&self) -> usize;
 
+    /// Number of known living cells in each generation,
+    /// from generation `0` to generation `period - 1`.
+    ///
+    /// For Generations rules, dying cells are not counted.
+    fn population_profile(&self) -> Vec<usize> {
+        (0..self.config().period)
+            .map(|t| self.cell_count_gen(t))
+            .collect()
+    }
+
+    /// Maximum number of known living cells in all generation.
+    ///
+    /// The minimum is given by `cell_count`.
+    ///
+    /// For Generations rules, dying cells are not counted.
+    fn max_population(&self) -> usize {
+        self.population_profile().into_iter().max().unwrap_or(0)
+    }
+
+    /// The actual period of the pattern, which divides the given period.
+    ///
+    /// It is the minimal `t` such that generation `t` is generation 0
+    /// moved by some rotation, reflection and translation,
+    /// where repeating this movement `period / t` times gives
+    /// the translation and the transformation in the configuration.
+    /// For example, a c/2 spaceship found in a 2c/4 search
+    /// has actual period 2.
+    ///
+    /// Only meaningful when there are no unknown cells, e.g., after a result
+    /// is found.
+    fn actual_period(&self) -> isize;
+
     /// Number of conflicts during the search.
     fn conflicts(&self) -> u64;
 
+    /// Statistics of the search, including the number of conflicts.
+    fn statistics(&self) -> Statistics;
+
+    /// Why the search returns `Status::None`.
+    ///
+    /// Tells whether such pattern does not exist at all,
+    /// or all the results have been found,
+    /// or no results are smaller than the last one when `reduce_max`
+    /// is set.
+    ///
+    /// Only meaningful after `search` returns `Status::None`.
+    fn none_reason(&self) -> NoneReason;
+
+    /// Makes a deep copy of the search, which continues the search
+    /// in the same way as the original one.
+    ///
+    /// This rebuilds the world and replays all the known cells,
+    /// so it costs about as much as creating a new world.
+    /// The progress callback is not copied, and the copy has its own
+    /// abort handle. The random number generator is copied, so with
+    /// `NewState::Random`, the copy makes the same random choices.
+    fn boxed_clone(&self) -> Box<dyn Search>;
+
+    /// An estimate of the fraction of the search space that has been
+    /// explored, between `0` and `1`, e.g., for a progress bar.
+    ///
+    /// This is only a heuristic, and may jump when the search backtracks.
+    /// See `World::progress` for details.
+    fn progress(&self) -> f64;
+
+    /// A flag that stops the search when it is set to `true`,
+    /// e.g., from another thread or a Ctrl-C handler.
+    ///
+    /// The search checks the flag once per step, and returns
+    /// `Status::Paused` if it is set. The flag is cleared at the same time,
+    /// so the search can be resumed by calling `search` again.
+    fn abort_handle(&self) -> Arc<AtomicBool>;
+
+    /// Why a cell has its current state.
+    ///
+    /// Returns `Ok(None)` if the cell is unknown,
+    /// and an error if there is no such cell.
+    fn cell_reason(&self, coord: Coord) -> Result<Option<SetReason>, Error>;
+
+    /// Sets a callback that is called with the statistics of the search
+    /// every `interval` conflicts during `search`.
+    ///
+    /// This can be used to show the progress of a long search.
+    /// An `interval` of `0` is treated as `1`.
+    fn set_progress_callback(&mut self, interval: u64, callback: ProgressCallback);
+
     /// Set the max cell counts.
     ///
     /// Currently this is the only parameter that you can change
     /// during the search.
+    ///
+    /// Lowering the limit keeps the current position of the search.
+    /// Raising the limit this way does not revisit the branches that
+    /// were already pruned by the old limit.
     fn set_max_cell_count(&mut self, max_cell_count: Option<usize>);
 
+    /// Set the max cell counts, and restarts the search from the beginning
+    /// if the new limit is higher than the old one.
+    ///
+    /// Lowering the limit is the same as `set_max_cell_count`.
+    /// Raising the limit clears all the cells except the known ones,
+    /// so branches pruned by the old limit are searched again,
+    /// and results that were already found will be found again.
+    /// The statistics and the saved phases are kept.
+    ///
+    /// Returns an error if the cells fixed by the configuration
+    /// or the known cells lead to a conflict when they are set again.
+    fn reset_max_cell_count(&mut self, max_cell_count: Option<usize>) -> Result<(), Error>;
+
+    /// Resets the search to the beginning, as if the world were created
+    /// again from its configuration, but reuses the cells that
+    /// are already allocated. This is faster than `Config::world` when
+    /// the configuration is not changed.
+    ///
+    /// The statistics, the activities and the saved phases are reset,
+    /// and results that were already found will be found again.
+    /// The progress callback and the abort handle are kept.
+    ///
+    /// The configuration is the one that the world was created with,
+    /// including the cells set by `try_set_cell` and the max cell count
+    /// set by `set_max_cell_count` or `reset_max_cell_count`.
+    /// The max cell count lowered by `reduce_max` is restored.
+    /// Returns an error if the known cells are inconsistent with it,
+    /// e.g., if they contain more living cells than a lowered
+    /// `max_cell_count`.
+    fn reset(&mut self) -> Result<(), Error>;
+
     #[cfg(feature = "serialize")]
     /// Saves the world as a `WorldSer`,
     /// which can be easily serialized.
     fn ser(&self) -> WorldSer;
 
+    #[cfg(feature = "compressed")]
+    /// Saves the world in a compact binary format, compressed with gzip.
+    ///
+    /// Use `WorldSer::from_compressed` to load it.
+    fn ser_compressed(&self) -> Vec<u8> {
+        self.ser().to_compressed()
+    }
+
+    #[cfg(feature = "trace")]
+    /// Starts recording the changes of the states of the cells,
+    /// keeping only the last `capacity` changes.
+    ///
+    /// `0` stops the recording. The recorded changes are cleared.
+    fn set_trace_capacity(&mut self, capacity: usize);
+
+    #[cfg(feature = "trace")]
+    /// The recorded changes of the states of the cells, oldest first,
+    /// including the cells cleared by backtracking.
+    ///
+    /// This can be used to replay how the search explores the world.
+    fn trace(&self) -> Vec<TraceEvent>;
+
+    #[cfg(feature = "apgcode")]
+    /// The [apgcode](https://conwaylife.com/wiki/Apgcode) of the pattern.
+    ///
+    /// Returns `None` if the pattern contains unknown cells or is empty.
+    fn apgcode(&self) -> Option<String>;
+
+    /// The state of a cell relative to the background of its generation.
+    ///
+    /// For rules with `B0`, the background changes from generation to
+    /// generation. Here cells in the background state are shown as `Dead`,
+    /// and dead cells are shown in the background state, so that a pattern
+    /// looks the same in every generation. For rules without `B0`,
+    /// it is the same as `cell_state`.
+    fn cell_state_relative(&self, coord: Coord) -> Result<CellState, Error> {
+        let (_, _, t) = coord;
+        let background = self.get_cell_state((-1, -1, t.rem_euclid(self.config().period)))?;
+        let state = self.cell_state(coord)?;
+        Ok(match background {
+            Some(background) if background != State(0) => {
+                if state == CellState::from(Some(background)) {
+                    CellState::Dead
+                } else if state == CellState::Dead {
+                    CellState::from(Some(background))
+                } else {
+                    state
+                }
+            }
+            _ => state,
+        })
+    }
+
+    /// The states of all cells in some generation, row by row.
+    ///
+    /// For Generations rules, dying cells are `CellState::Dying`
+    /// with their states, so that they can be told apart.
+    fn cell_states_gen(&self, t: isize) -> Vec<Vec<CellState>> {
+        (0..self.config().height)
+            .map(|y| {
+                (0..self.config().width)
+                    .map(|x| self.cell_state((x, y, t)).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// An iterator over all cells in some generation, row by row,
+    /// with their coordinates and states.
+    ///
+    /// Unknown cells have the state `None`.
+    fn cells(&self, t: isize) -> Box<dyn Iterator<Item = (Coord, Option<State>)> + '_> {
+        let (width, height, _) = self.dimensions();
+        Box::new((0..height).flat_map(move |y| {
+            (0..width).map(move |x| ((x, y, t), self.get_cell_state((x, y, t)).unwrap()))
+        }))
+    }
+
     /// Displays the whole world in some generation,
     /// in a mix of [Plaintext](https://conwaylife.com/wiki/Plaintext) and
     /// [RLE](https://conwaylife.com/wiki/Rle) format.
@@ -75,39 +663,204 @@ pub trait Search {
     /// * Each line is ended with `$`;
     /// * The whole pattern is ended with `!`.
     fn rle_gen(&self, t: isize) -> String {
-        let mut str = String::new();
-        writeln!(
-            str,
-            "x = {}, y = {}, rule = {}",
-            self.config().width,
-            self.config().height,
-            self.config().rule_string
-        )
-        .unwrap();
-        for y in 0..self.config().height {
-            for x in 0..self.config().width {
-                let state = self.get_cell_state((x, y, t)).unwrap();
-                match state {
-                    Some(DEAD) => str.push('.'),
-                    Some(ALIVE) => {
-                        if self.is_gen_rule() {
-                            str.push('A')
+        self.rle_gen_with(t, false)
+    }
+
+    /// The same as `rle_gen`, but if `relative` is `true`,
+    /// the cells are shown relative to the background.
+    /// See `cell_state_relative` for details.
+    ///
+    /// This only makes a difference for rules with `B0`.
+    fn rle_gen_with(&self, t: isize, relative: bool) -> String {
+        let cells = (0..self.config().height)
+            .map(|y| {
+                (0..self.config().width)
+                    .map(|x| {
+                        if relative {
+                            self.cell_state_relative((x, y, t)).unwrap()
                         } else {
-                            str.push('o')
+                            self.cell_state((x, y, t)).unwrap()
                         }
-                    }
-                    Some(State(i)) => str.push((b'A' + i as u8 - 1) as char),
-                    _ => str.push('?'),
+                    })
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        rle_gen_cells(self, &cells)
+    }
+
+    /// The states of all cells in some generation, row by row,
+    /// with **unknown** cells resolved to alive if `unknown_alive`
+    /// is `true`, or to dead otherwise.
+    ///
+    /// This is how a maximal partial result is shown: resolving
+    /// unknown cells to alive shows every cell that might still be alive.
+    fn max_partial_cells(&self, t: isize, unknown_alive: bool) -> Vec<Vec<CellState>> {
+        let mut cells = self.cell_states_gen(t);
+        for state in cells.iter_mut().flatten() {
+            if *state == CellState::Unknown {
+                *state = if unknown_alive {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
                 };
             }
-            if y == self.config().height - 1 {
-                str.push('!')
-            } else {
-                str.push('$')
-            };
-            str.push('\n');
         }
-        str
+        cells
+    }
+
+    /// Displays a maximal partial result in some generation,
+    /// in the same format as `rle_gen`.
+    ///
+    /// **Unknown** cells are resolved as in `max_partial_cells`,
+    /// so there is no `?` in the output.
+    fn max_partial(&self, t: isize, unknown_alive: bool) -> String {
+        rle_gen_cells(self, &self.max_partial_cells(t, unknown_alive))
+    }
+
+    /// Exports the whole world in some generation in standard
+    /// [RLE](https://conwaylife.com/wiki/Rle) format,
+    /// which can be pasted into Golly.
+    ///
+    /// Unlike `rle_gen`, identical cells are run-length encoded,
+    /// and **unknown** cells are treated as dead.
+    ///
+    /// * For rules with 2 states, **dead** cells are represented by `b`,
+    ///   **living** cells by `o`;
+    /// * For rules with more states, **dead** cells are represented by `.`,
+    ///   **living** cells by `A`, **dying** cells by uppercase letters
+    ///   starting from `B`.
+    fn rle(&self, t: isize) -> String {
+        self.rle_with(t, false)
+    }
+
+    /// The same as `rle`, but if `relative` is `true`,
+    /// the cells are exported relative to the background.
+    /// See `cell_state_relative` for details.
+    ///
+    /// This only makes a difference for rules with `B0`.
+    /// Note that the result is not a valid pattern of the rule itself
+    /// when the background is not dead.
+    fn rle_with(&self, t: isize, relative: bool) -> String {
+        let mut buf = Vec::new();
+        self.write_rle_with(t, relative, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Writes the whole world in some generation in RLE format
+    /// to a writer, e.g., a file that collects many patterns.
+    ///
+    /// The output is the same as `rle`, but without building
+    /// the whole string in memory.
+    fn write_rle(&self, t: isize, w: &mut dyn io::Write) -> io::Result<()> {
+        self.write_rle_with(t, false, w)
+    }
+
+    /// The same as `write_rle`, but if `relative` is `true`,
+    /// the cells are exported relative to the background.
+    /// See `rle_with` for details.
+    fn write_rle_with(&self, t: isize, relative: bool, w: &mut dyn io::Write) -> io::Result<()> {
+        let (width, height, _) = self.dimensions();
+        write_rle_rect(self, t, relative, (0, 0, width, height), w)
+    }
+
+    /// The smallest rectangle `(x, y, width, height)` that contains
+    /// all the living and dying cells in generation `t`,
+    /// or in all generations if `t` is `None`.
+    ///
+    /// Returns `None` if there is no such cell.
+    fn pattern_bounding_box(&self, t: Option<isize>) -> Option<(isize, isize, isize, isize)> {
+        let (width, height, period) = self.dimensions();
+        let gens = match t {
+            Some(t) => t..t + 1,
+            None => 0..period,
+        };
+        let mut bounds: Option<(isize, isize, isize, isize)> = None;
+        for t in gens {
+            for y in 0..height {
+                for x in 0..width {
+                    if let CellState::Alive | CellState::Dying(_) =
+                        self.cell_state((x, y, t)).unwrap()
+                    {
+                        bounds = Some(match bounds {
+                            Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                            None => (x, y, x, y),
+                        });
+                    }
+                }
+            }
+        }
+        bounds.map(|(x0, y0, x1, y1)| (x0, y0, x1 - x0 + 1, y1 - y0 + 1))
+    }
+
+    /// Exports some generation in RLE format, trimmed to the living
+    /// and dying cells, so that it can be pasted without cropping.
+    ///
+    /// If `all_gens` is `true`, the pattern is trimmed to the bounding
+    /// box of all generations instead of generation `t` alone,
+    /// so that all phases of an oscillator have the same size and
+    /// position. An empty pattern gives an empty RLE.
+    ///
+    /// Otherwise the same as `rle`.
+    fn rle_trimmed(&self, t: isize, all_gens: bool) -> String {
+        let mut buf = Vec::new();
+        self.write_rle_trimmed(t, all_gens, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Writes some generation in RLE format, trimmed to the living
+    /// and dying cells, to a writer.
+    ///
+    /// See `rle_trimmed` for details.
+    fn write_rle_trimmed(&self, t: isize, all_gens: bool, w: &mut dyn io::Write) -> io::Result<()> {
+        let gen = if all_gens { None } else { Some(t) };
+        let rect = self.pattern_bounding_box(gen).unwrap_or((0, 0, 0, 0));
+        write_rle_rect(self, t, false, rect, w)
+    }
+
+    /// Exports the whole world in some generation in RLE format of
+    /// the [LifeHistory](https://conwaylife.com/wiki/LifeHistory) rule,
+    /// with some cells marked, e.g., for posting on forums.
+    ///
+    /// Living cells are in state `1`, other cells in state `0`.
+    /// `marks` gives the LifeHistory states of the marked cells, e.g.,
+    /// `3` for marked living cells, `4` for marked dead cells.
+    /// Marks in other generations, and marks with states larger than `6`,
+    /// are ignored.
+    fn lifehistory_rle(&self, t: isize, marks: &[(Coord, u8)]) -> String {
+        let (width, height, _) = self.dimensions();
+        let marks: HashMap<_, _> = marks
+            .iter()
+            .filter(|&&((_, _, mark_t), state)| mark_t == t && state <= 6)
+            .map(|&((x, y, _), state)| ((x, y), state))
+            .collect();
+        let mut buf = format!("x = {}, y = {}, rule = LifeHistory\n", width, height).into_bytes();
+        write_rle_cells(&mut buf, width, height, '.', |x, y| {
+            let state = marks.get(&(x, y)).copied().unwrap_or_else(|| {
+                match self.cell_state((x, y, t)).unwrap() {
+                    CellState::Alive => 1,
+                    _ => 0,
+                }
+            });
+            match state {
+                0 => '.',
+                n => (b'A' + n - 1) as char,
+            }
+        })
+        .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Exports some generation in the given format.
+    ///
+    /// This is a single entry point for `rle`, `rle_trimmed`,
+    /// `to_plaintext` and `to_macrocell`, e.g., for a menu of formats.
+    fn export(&self, t: isize, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Rle => self.rle(t),
+            OutputFormat::RleTrimmed => self.rle_trimmed(t, false),
+            OutputFormat::Plaintext => self.to_plaintext(t),
+            OutputFormat::Macrocell => self.to_macrocell(t),
+        }
     }
 
     /// Displays the whole world in some generation in
@@ -118,16 +871,67 @@ pub trait Search {
     /// * **Unknown** cells are represented by `?`.
     fn plaintext_gen(&self, t: isize) -> String {
         let mut str = String::new();
-        for y in 0..self.config().height {
-            for x in 0..self.config().width {
-                let state = self.get_cell_state((x, y, t)).unwrap();
-                match state {
-                    Some(DEAD) => str.push('.'),
-                    Some(_) => str.push('o'),
-                    None => str.push('?'),
-                };
-            }
-            str.push('\n');
+        write_plaintext(self, t, 'o', &mut str);
+        str
+    }
+
+    /// Exports the whole world in some generation in
+    /// [Plaintext](https://conwaylife.com/wiki/Plaintext) format,
+    /// with a comment line starting with `!` that records the rule.
+    ///
+    /// * **Dead** cells are represented by `.`;
+    /// * **Living** and **Dying** cells are represented by `O`;
+    /// * **Unknown** cells are represented by `?`.
+    ///
+    /// Note that `?` is not part of the Plaintext format. It is kept here
+    /// so that partial results can still be inspected.
+    fn to_plaintext(&self, t: isize) -> String {
+        let mut str = String::new();
+        writeln!(str, "!Rule: {}", self.config().rule_string).unwrap();
+        write_plaintext(self, t, 'O', &mut str);
+        str
+    }
+
+    /// Exports the whole world in some generation in Golly's
+    /// [macrocell](https://golly.sourceforge.net/Help/formats.html#mc) format,
+    /// which is more compact than RLE for large sparse patterns.
+    ///
+    /// Identical nodes of the quadtree are only written once.
+    /// **Unknown** cells are treated as dead.
+    /// The upper left corner of the world is the upper left corner
+    /// of the root node.
+    fn to_macrocell(&self, t: isize) -> String {
+        let cells: Vec<Vec<usize>> = self
+            .cell_states_gen(t)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|state| match state {
+                        CellState::Dead | CellState::Unknown => 0,
+                        CellState::Alive => 1,
+                        CellState::Dying(i) => i,
+                    })
+                    .collect()
+            })
+            .collect();
+        let is_gen = self.is_gen_rule();
+        let size = self.config().width.max(self.config().height) as usize;
+        let mut level = if is_gen { 1 } else { 3 };
+        while 1 << level < size {
+            level += 1;
+        }
+        let mut macrocell = Macrocell {
+            cells: &cells,
+            is_gen,
+            lines: Vec::new(),
+            indices: HashMap::new(),
+        };
+        macrocell.node(0, 0, level);
+
+        let mut str = String::from("[M2] (rlifesrc)\n");
+        writeln!(str, "#R {}", self.config().rule_string).unwrap();
+        for line in macrocell.lines {
+            writeln!(str, "{}", line).unwrap();
         }
         str
     }
@@ -139,6 +943,14 @@ impl<'a, R: Rule> Search for World<'a, R> {
         self.search(max_step)
     }
 
+    fn search_with_callback(
+        &mut self,
+        max_step: Option<u64>,
+        on_found: &mut dyn FnMut(&dyn Search) -> ControlFlow<()>,
+    ) -> Status {
+        self.search_with_callback(max_step, on_found)
+    }
+
     fn get_cell_state(&self, coord: Coord) -> Result<Option<State>, Error> {
         self.get_cell_state(coord)
     }
@@ -156,23 +968,232 @@ impl<'a, R: Rule> Search for World<'a, R> {
     }
 
     fn cell_count_gen(&self, t: isize) -> usize {
-        self.cell_count[t as usize]
+        self.cell_count[t.rem_euclid(self.config.period) as usize]
     }
 
     fn cell_count(&self) -> usize {
         self.cell_count()
     }
 
+    fn actual_period(&self) -> isize {
+        self.actual_period()
+    }
+
     fn conflicts(&self) -> u64 {
         self.conflicts
     }
 
+    fn statistics(&self) -> Statistics {
+        self.statistics()
+    }
+
+    fn none_reason(&self) -> NoneReason {
+        self.none_reason()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Search> {
+        Box::new(self.clone_world())
+    }
+
+    fn progress(&self) -> f64 {
+        self.progress()
+    }
+
+    fn abort_handle(&self) -> Arc<AtomicBool> {
+        self.abort_handle()
+    }
+
+    fn cell_reason(&self, coord: Coord) -> Result<Option<SetReason>, Error> {
+        self.cell_reason(coord)
+    }
+
+    fn set_progress_callback(&mut self, interval: u64, callback: ProgressCallback) {
+        self.set_progress_callback(interval, callback)
+    }
+
+    fn try_set_cell(&mut self, coord: Coord, state: State) -> Result<(), Error> {
+        self.try_set_cell(coord, state)
+    }
+
     fn set_max_cell_count(&mut self, max_cell_count: Option<usize>) {
         self.set_max_cell_count(max_cell_count)
     }
 
+    fn reset_max_cell_count(&mut self, max_cell_count: Option<usize>) -> Result<(), Error> {
+        self.reset_max_cell_count(max_cell_count)
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        self.reset()
+    }
+
     #[cfg(feature = "serialize")]
     fn ser(&self) -> WorldSer {
         self.ser()
     }
+
+    #[cfg(feature = "trace")]
+    fn set_trace_capacity(&mut self, capacity: usize) {
+        self.set_trace_capacity(capacity)
+    }
+
+    #[cfg(feature = "trace")]
+    fn trace(&self) -> Vec<TraceEvent> {
+        self.trace()
+    }
+
+    #[cfg(feature = "apgcode")]
+    fn apgcode(&self) -> Option<String> {
+        self.apgcode()
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl dyn Search {
+    /// An iterator over all the results of the search.
+    ///
+    /// See `Results` for details.
+    pub fn results(&mut self) -> Results<'_> {
+        Results {
+            search: self,
+            done: false,
+        }
+    }
+
+    /// An iterator over all the results of the search,
+    /// skipping results that are the same pattern as an earlier one.
+    ///
+    /// See `DedupResults` for details.
+    pub fn results_deduplicated(&mut self) -> DedupResults<'_> {
+        DedupResults {
+            search: self,
+            done: false,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+/// An iterator over all the results of a search,
+/// created by the `results` method of `Search`.
+///
+/// Each call of `next` drives the search until the next result is found,
+/// and yields it as a `WorldSer`. The iteration stops when there are no
+/// more results.
+///
+/// The results are given in the natural order of the search.
+/// They are not deduplicated by symmetry or translation;
+/// see `DedupResults` for that.
+#[cfg(feature = "serialize")]
+pub struct Results<'s> {
+    /// The search.
+    search: &'s mut dyn Search,
+
+    /// Whether the search is finished.
+    done: bool,
+}
+
+/// The canonical form of a pattern, used by `DedupResults`.
+///
+/// The background state, and the cells that differ from the background
+/// with their states.
+#[cfg(feature = "serialize")]
+type CanonicalForm = (usize, Vec<((isize, isize), usize)>);
+
+/// A canonical form of the current pattern of the search, which is
+/// the same for patterns that only differ by a rotation, a reflection,
+/// a translation or a phase.
+///
+/// Returns `None` if the pattern contains unknown cells.
+#[cfg(feature = "serialize")]
+fn canonical_form(search: &dyn Search) -> Option<CanonicalForm> {
+    let config = search.config();
+    let mut forms = Vec::new();
+    for t in 0..config.period {
+        // Cells outside of the world always have the background state.
+        let background = search.get_cell_state((-1, -1, t)).ok()??;
+        let mut cells = Vec::new();
+        for x in 0..config.width {
+            for y in 0..config.height {
+                let state = search.get_cell_state((x, y, t)).ok()??;
+                if state != background {
+                    cells.push(((x, y), state.0));
+                }
+            }
+        }
+        for f in ISOMETRIES.iter() {
+            let mut image: Vec<_> = cells.iter().map(|&(p, s)| (f(p), s)).collect();
+            let min_x = image.iter().map(|&((x, _), _)| x).min().unwrap_or(0);
+            let min_y = image.iter().map(|&((_, y), _)| y).min().unwrap_or(0);
+            for ((x, y), _) in image.iter_mut() {
+                *x -= min_x;
+                *y -= min_y;
+            }
+            image.sort_unstable();
+            forms.push((background.0, image));
+        }
+    }
+    forms.into_iter().min()
+}
+
+/// An iterator over the results of a search without duplicates,
+/// created by the `results_deduplicated` method of `Search`.
+///
+/// Like `Results`, but a result is skipped if it is the same pattern
+/// as an earlier result, up to rotations, reflections, translations
+/// and phases. Each pattern is compared by a canonical form: the least
+/// among its images under the 8 rotations and reflections in all
+/// generations, moved to the origin.
+///
+/// Note that the canonical forms of all the yielded results are kept
+/// in memory until the iterator is dropped. Each of them takes about
+/// as much memory as the living cells of the pattern.
+#[cfg(feature = "serialize")]
+pub struct DedupResults<'s> {
+    /// The search.
+    search: &'s mut dyn Search,
+
+    /// Whether the search is finished.
+    done: bool,
+
+    /// Canonical forms of the results that have been yielded.
+    seen: HashSet<CanonicalForm>,
+}
+
+#[cfg(feature = "serialize")]
+impl<'s> Iterator for DedupResults<'s> {
+    type Item = WorldSer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if let Status::Found = self.search.search(None) {
+                let is_new = match canonical_form(self.search) {
+                    Some(form) => self.seen.insert(form),
+                    None => true,
+                };
+                if is_new {
+                    return Some(self.search.ser());
+                }
+            } else {
+                self.done = true;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'s> Iterator for Results<'s> {
+    type Item = WorldSer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Status::Found = self.search.search(None) {
+            Some(self.search.ser())
+        } else {
+            self.done = true;
+            None
+        }
+    }
 }
@@ -2,43 +2,169 @@
 
 use crate::{
     cells::{CellRef, Coord, LifeCell, State, DEAD},
-    config::{Config, SearchOrder, Symmetry, Transform},
+    config::{Boundary, Config, CountMode, DecisionHeuristic, SearchOrder, Symmetry, Transform},
     error::Error,
+    heap::ActivityHeap,
     rules::Rule,
-    search::{Reason, SetCell},
+    search::{ProgressCallback, Reason, SetCell, RESTART_UNIT},
 };
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+#[cfg(feature = "trace")]
+use crate::search::TraceEvent;
+#[cfg(feature = "trace")]
+use std::collections::VecDeque;
+
+/// Coordinates of a cell, without the time coordinate.
+pub(crate) type Point = (isize, isize);
+
+/// The eight isometries of the square grid that fix the origin.
+pub(crate) const ISOMETRIES: [fn(Point) -> Point; 8] = [
+    |(x, y)| (x, y),
+    |(x, y)| (-x, y),
+    |(x, y)| (x, -y),
+    |(x, y)| (-x, -y),
+    |(x, y)| (y, x),
+    |(x, y)| (-y, x),
+    |(x, y)| (y, -x),
+    |(x, y)| (-y, -x),
+];
 
 /// The world.
 pub struct World<'a, R: Rule> {
     /// World configuration.
     pub(crate) config: Config,
 
+    /// The configuration that `reset` goes back to.
+    ///
+    /// It is the configuration that the world is created with,
+    /// with the cells set by `try_set_cell` and the max cell count
+    /// set by `set_max_cell_count` or `reset_max_cell_count`,
+    /// but without the changes made by `reduce_max` during the search.
+    pub(crate) init_config: Config,
+
     /// The rule of the cellular automaton.
     pub(crate) rule: R,
 
+    /// The width of the border of background cells around the search range.
+    ///
+    /// It is the range of the neighborhood of the rule, so that every cell
+    /// in the search range has all its neighbors.
+    pub(crate) border: isize,
+
     /// A vector that stores all the cells in the search range.
     ///
     /// This vector will not be moved after its creation.
     /// All the cells will live throughout the lifetime of the world.
     // So the unsafe codes below are actually safe.
-    cells: Vec<LifeCell<'a, R>>,
+    pub(crate) cells: Vec<LifeCell<'a, R>>,
 
     /// A list of references to cells sorted by the search order.
     ///
     /// Used to find unknown cells.
-    search_list: Vec<CellRef<'a, R>>,
+    pub(crate) search_list: Vec<CellRef<'a, R>>,
 
     /// Number of known living cells in each generation.
     ///
     /// For Generations rules, dying cells are not counted.
     pub(crate) cell_count: Vec<usize>,
 
+    /// Number of known living cells in each generation that are
+    /// representatives of their orbits under the symmetry.
+    ///
+    /// See `CountMode::OrbitRepresentatives`.
+    pub(crate) orbit_cell_count: Vec<usize>,
+
     /// Number of unknown or living cells on the first row or column.
     pub(crate) front_cell_count: usize,
 
+    /// Number of unknown or living cells in generation 0.
+    pub(crate) gen0_cell_count: usize,
+
     /// Number of conflicts during the search.
     pub(crate) conflicts: u64,
 
+    /// Number of decisions during the search.
+    pub(crate) decisions: u64,
+
+    /// Number of cells examined by `proceed` during the search.
+    pub(crate) propagations: u64,
+
+    /// Maximal length of the `set_stack` during the search.
+    pub(crate) max_depth: usize,
+
+    /// Current decision level, i.e., the number of cells in the `set_stack`
+    /// that are decided by choice.
+    pub(crate) decision_level: usize,
+
+    /// Maximal decision level during the search.
+    pub(crate) max_decision_level: usize,
+
+    /// Number of results found during the search.
+    pub(crate) results: u64,
+
+    /// Number of restarts during the search.
+    pub(crate) restarts: u64,
+
+    /// Number of decisions that reuse a saved phase.
+    pub(crate) saved_phases: u64,
+
+    /// The number of conflicts at which the search will restart.
+    ///
+    /// `None` means that the search will not restart.
+    pub(crate) next_restart: Option<u64>,
+
+    /// The amount by which the activity of a cell is increased
+    /// when it is involved in a conflict.
+    ///
+    /// Used by `DecisionHeuristic::Vsids`.
+    pub(crate) activity_inc: f64,
+
+    /// The cells in the `search_list` ordered by activity.
+    ///
+    /// Used by `DecisionHeuristic::Vsids`.
+    pub(crate) activity_heap: ActivityHeap,
+
+    /// The random number generator for `NewState::Random`
+    /// and `NewState::RandomBiased`.
+    ///
+    /// Seeded by `seed` of the configuration if it is set.
+    pub(crate) rng: StdRng,
+
+    /// The cells in the `forbidden` pattern of the configuration,
+    /// and their forbidden states.
+    pub(crate) forbidden: Vec<(CellRef<'a, R>, State)>,
+
+    /// A callback that is called with the statistics of the search
+    /// every time the number of conflicts reaches a multiple of the
+    /// given interval.
+    pub(crate) progress: Option<(u64, ProgressCallback)>,
+
+    /// A flag that can be set from another thread to stop the search.
+    ///
+    /// See `abort_handle` for details.
+    pub(crate) abort: Arc<AtomicBool>,
+
+    /// The recorded changes of the states of the cells.
+    ///
+    /// See `set_trace_capacity` for details.
+    #[cfg(feature = "trace")]
+    pub(crate) trace: VecDeque<TraceEvent>,
+
+    /// The number of changes to keep in the `trace`.
+    ///
+    /// `0` means that nothing is recorded.
+    #[cfg(feature = "trace")]
+    pub(crate) trace_capacity: usize,
+
     /// A stack to record the cells whose values are set during the search.
     ///
     /// The cells in this stack always have known states.
@@ -58,11 +184,78 @@ pub struct World<'a, R: Rule> {
 }
 
 impl<'a, R: Rule> World<'a, R> {
+    /// The width of the border of background cells around the search range.
+    fn border(rule: &R) -> isize {
+        rule.nbhd()
+            .iter()
+            .map(|&(x, y)| x.abs().max(y.abs()))
+            .max()
+            .unwrap_or(0)
+            .max(1)
+    }
+
+    /// A random number generator seeded by `seed` of the configuration,
+    /// or from the system entropy if there is no seed.
+    fn new_rng(config: &Config) -> StdRng {
+        match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        }
+    }
+
+    /// The number of cells in the world, including the border.
+    ///
+    /// Returns `None` if the coordinates of the cells, translated by
+    /// `dx` and `dy`, would overflow, or if the cells are too many
+    /// to fit in memory.
+    fn size(config: &Config, rule: &R) -> Option<usize> {
+        let border = Self::border(rule);
+        let width = config.width.checked_add(2 * border)?;
+        let height = config.height.checked_add(2 * border)?;
+        width.checked_add(config.dx.checked_abs()?)?;
+        height.checked_add(config.dy.checked_abs()?)?;
+        let size = width.checked_mul(height)?.checked_mul(config.period)?;
+        size.checked_mul(mem::size_of::<LifeCell<R>>() as isize)?;
+        Some(size as usize)
+    }
+
+    /// Whether the transformation and translation after a period,
+    /// the symmetries, and the wrapping of a torus map cells with even
+    /// `x + y` to cells with even `x + y`, as a checkerboard rule requires.
+    ///
+    /// All these maps are isometries of the grid, which change the parity
+    /// of `x + y` either everywhere or nowhere, so it suffices to look at
+    /// the cell `(0, 0)`.
+    fn preserves_colors(config: &Config) -> bool {
+        let (width, height) = (config.width, config.height);
+        let even = |(x, y, _): Coord| (x + y).rem_euclid(2) == 0;
+        even(config.translate((0, 0, -config.period)))
+            && config.symmetry_coords((0, 0, 0)).into_iter().all(even)
+            && (config.boundary != Boundary::Torus || (width % 2 == 0 && height % 2 == 0))
+    }
+
     /// Creates a new world from the configuration and the rule.
-    pub fn new(config: &Config, rule: R) -> Self {
+    ///
+    /// The known cells of the configuration are not set yet.
+    ///
+    /// Returns `Error::WorldSizeError` if the world is too large.
+    /// Without a transformation, the translation is also checked against
+    /// the speed of light of the rule: a pattern cannot move more than the
+    /// range of the neighborhood in each generation, in either direction.
+    pub fn new(config: &Config, rule: R) -> Result<Self, Error> {
         let search_order = config.auto_search_order();
 
-        let size = ((config.width + 2) * (config.height + 2) * config.period) as usize;
+        let border = Self::border(&rule);
+        let size = Self::size(config, &rule).ok_or(Error::WorldSizeError)?;
+        if rule.is_checkerboard() && !Self::preserves_colors(config) {
+            return Err(Error::CheckerboardColorError);
+        }
+        if config.transform == Transform::Id {
+            let max_shift = config.period.saturating_mul(border);
+            if config.dx.abs() > max_shift || config.dy.abs() > max_shift {
+                return Err(Error::SpeedOfLightError);
+            }
+        }
         let mut cells = Vec::with_capacity(size);
 
         // Whether to consider only the first generation of the front.
@@ -89,13 +282,16 @@ impl<'a, R: Rule> World<'a, R> {
             _ => front_gen0,
         };
 
+        // The number of rows or columns in the front.
+        let lines = config.front_lines as isize;
+
         // Fills the vector with dead cells,
-        // and checks whether it is on the first row or column.
+        // and checks whether it is on the front.
         //
         // If the rule contains `B0`, then fills the odd generations
         // with living cells instead.
-        for x in -1..=config.width {
-            for y in -1..=config.height {
+        for x in -border..config.width + border {
+            for y in -border..config.height + border {
                 for t in 0..config.period {
                     let state = if rule.has_b0() {
                         State(t as usize % rule.gen())
@@ -107,29 +303,31 @@ impl<'a, R: Rule> World<'a, R> {
                     } else {
                         DEAD
                     };
-                    let mut cell = LifeCell::new((x, y, t), state, succ_state);
+                    let mut cell = LifeCell::new((x, y, t), state, succ_state, &rule);
                     match search_order {
                         SearchOrder::ColumnFirst => {
                             if front_gen0 {
-                                if x == (config.dx - 1).max(0)
+                                let start = (config.dx - 1).max(0);
+                                if (start..start + lines).contains(&x)
                                     && t == 0
                                     && (!front_half || 2 * y < config.height)
                                 {
                                     cell.is_front = true
                                 }
-                            } else if x == 0 {
+                            } else if (0..lines).contains(&x) {
                                 cell.is_front = true
                             }
                         }
                         SearchOrder::RowFirst => {
                             if front_gen0 {
-                                if y == (config.dy - 1).max(0)
+                                let start = (config.dy - 1).max(0);
+                                if (start..start + lines).contains(&y)
                                     && t == 0
                                     && (!front_half || 2 * x < config.width)
                                 {
                                     cell.is_front = true
                                 }
-                            } else if y == 0 {
+                            } else if (0..lines).contains(&y) {
                                 cell.is_front = true
                             }
                         }
@@ -140,14 +338,41 @@ impl<'a, R: Rule> World<'a, R> {
             }
         }
 
-        World {
+        Ok(World {
             config: config.clone(),
+            init_config: config.clone(),
             rule,
+            border,
             cells,
             search_list: Vec::with_capacity(size),
             cell_count: vec![0; config.period as usize],
+            orbit_cell_count: vec![0; config.period as usize],
             front_cell_count: 0,
+            gen0_cell_count: 0,
             conflicts: 0,
+            decisions: 0,
+            propagations: 0,
+            max_depth: 0,
+            decision_level: 0,
+            max_decision_level: 0,
+            results: 0,
+            restarts: 0,
+            saved_phases: 0,
+            next_restart: if config.restart {
+                Some(RESTART_UNIT)
+            } else {
+                None
+            },
+            activity_inc: 1.0,
+            activity_heap: ActivityHeap::default(),
+            rng: Self::new_rng(config),
+            forbidden: Vec::new(),
+            progress: None,
+            abort: Arc::new(AtomicBool::new(false)),
+            #[cfg(feature = "trace")]
+            trace: VecDeque::new(),
+            #[cfg(feature = "trace")]
+            trace_capacity: 0,
             set_stack: Vec::with_capacity(size),
             check_index: 0,
             search_index: 0,
@@ -155,33 +380,42 @@ impl<'a, R: Rule> World<'a, R> {
         .init_nbhd()
         .init_pred_succ()
         .init_sym()
+        .init_stator()
         .init_state()
         .init_search_order(search_order)
+        .init_forbidden())
     }
 
     /// Links the cells to their neighbors.
     ///
     /// Note that for cells on the edges of the search range,
     /// some neighbors might point to `None`.
+    ///
+    /// If the world is a torus, the neighbors of the cells in the world
+    /// wrap around the edges, so the cells outside are never reached.
     fn init_nbhd(mut self) -> Self {
-        const NBHD: [(isize, isize); 8] = [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ];
-        for x in -1..=self.config.width {
-            for y in -1..=self.config.height {
+        let nbhd = self.rule.nbhd();
+        let (width, height) = (self.config.width, self.config.height);
+        let torus = self.config.boundary == Boundary::Torus;
+        for x in -self.border..width + self.border {
+            for y in -self.border..height + self.border {
+                let wrap = torus && 0 <= x && x < width && 0 <= y && y < height;
                 for t in 0..self.config.period {
                     let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
-                    for (i, (nx, ny)) in NBHD.iter().enumerate() {
+                    // Avoids reallocations while pushing the neighbors.
+                    unsafe {
+                        let cell = cell_ptr.as_mut().unwrap();
+                        cell.nbhd.reserve_exact(nbhd.len());
+                    }
+                    for (nx, ny) in nbhd.iter() {
+                        let (nx, ny) = if wrap {
+                            ((x + nx).rem_euclid(width), (y + ny).rem_euclid(height))
+                        } else {
+                            (x + nx, y + ny)
+                        };
                         unsafe {
                             let cell = cell_ptr.as_mut().unwrap();
-                            cell.nbhd[i] = self.find_cell((x + nx, y + ny, t));
+                            cell.nbhd.push(self.find_cell((nx, ny, t)));
                         }
                     }
                 }
@@ -198,8 +432,8 @@ impl<'a, R: Rule> World<'a, R> {
     /// If the successor is out of the search range,
     /// then sets it to `None`.
     fn init_pred_succ(mut self) -> Self {
-        for x in -1..=self.config.width {
-            for y in -1..=self.config.height {
+        for x in -self.border..self.config.width + self.border {
+            for y in -self.border..self.config.height + self.border {
                 for t in 0..self.config.period {
                     let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
                     let cell = self.find_cell((x, y, t)).unwrap();
@@ -248,47 +482,24 @@ impl<'a, R: Rule> World<'a, R> {
     /// If some symmetric cell is out of the search range,
     /// then  marks the current cell as known.
     fn init_sym(mut self) -> Self {
-        for x in -1..=self.config.width {
-            for y in -1..=self.config.height {
+        for x in -self.border..self.config.width + self.border {
+            for y in -self.border..self.config.height + self.border {
                 for t in 0..self.config.period {
                     let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
                     let cell = self.find_cell((x, y, t)).unwrap();
 
-                    let sym_coords = match self.config.symmetry {
-                        Symmetry::C1 => vec![],
-                        Symmetry::C2 => {
-                            vec![(self.config.width - 1 - x, self.config.height - 1 - y, t)]
-                        }
-                        Symmetry::C4 => vec![
-                            (y, self.config.width - 1 - x, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                            (self.config.height - 1 - y, x, t),
-                        ],
-                        Symmetry::D2Row => vec![(x, self.config.height - 1 - y, t)],
-                        Symmetry::D2Col => vec![(self.config.width - 1 - x, y, t)],
-                        Symmetry::D2Diag => vec![(y, x, t)],
-                        Symmetry::D2Antidiag => {
-                            vec![(self.config.height - 1 - y, self.config.width - 1 - x, t)]
-                        }
-                        Symmetry::D4Ortho => vec![
-                            (self.config.width - 1 - x, y, t),
-                            (x, self.config.height - 1 - y, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                        ],
-                        Symmetry::D4Diag => vec![
-                            (y, x, t),
-                            (self.config.height - 1 - y, self.config.width - 1 - x, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                        ],
-                        Symmetry::D8 => vec![
-                            (y, self.config.width - 1 - x, t),
-                            (self.config.height - 1 - y, x, t),
-                            (self.config.width - 1 - x, y, t),
-                            (x, self.config.height - 1 - y, t),
-                            (y, x, t),
-                            (self.config.height - 1 - y, self.config.width - 1 - x, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                        ],
+                    let in_world =
+                        0 <= x && x < self.config.width && 0 <= y && y < self.config.height;
+                    // The cells outside of a torus are never reached,
+                    // and wrapping them would link them to the cells inside.
+                    let sym_coords = if in_world {
+                        self.config.symmetry_coords((x, y, t))
+                    } else {
+                        self.config.symmetry.coords(
+                            (x, y, t),
+                            self.config.width,
+                            self.config.height,
+                        )
                     };
                     for coord in sym_coords {
                         if 0 <= coord.0
@@ -300,29 +511,164 @@ impl<'a, R: Rule> World<'a, R> {
                                 let cell = cell_ptr.as_mut().unwrap();
                                 cell.sym.push(self.find_cell(coord).unwrap());
                             }
-                        } else if 0 <= x
-                            && x < self.config.width
-                            && 0 <= y
-                            && y < self.config.height
-                            && !self.set_stack.iter().any(|s| s.cell == cell)
-                        {
+                        } else if in_world && !self.set_stack.iter().any(|s| s.cell == cell) {
                             self.set_stack.push(SetCell::new(cell, Reason::Deduce));
                         }
                     }
+                    unsafe {
+                        let cell = cell_ptr.as_mut().unwrap();
+                        cell.is_orbit_rep = cell
+                            .sym
+                            .iter()
+                            .all(|sym| (sym.coord.0, sym.coord.1) >= (x, y));
+                    }
+                }
+            }
+        }
+        if self.config.symmetry_offset != (0, 0) {
+            self.init_orbit_reps();
+        }
+        self
+    }
+
+    /// Finds the representative of each orbit of the symmetry by following
+    /// the links to the symmetric cells.
+    ///
+    /// With a `symmetry_offset`, the transformations of the symmetry
+    /// are no longer closed under composition, so an orbit may contain
+    /// more cells than the images of one cell that `init_sym` checks.
+    /// The representative is the first cell of the orbit in `(x, y)` order.
+    fn init_orbit_reps(&mut self) {
+        let mut visited = vec![false; self.cells.len()];
+        let (border, height, period) = (self.border, self.config.height, self.config.period);
+        let index = |cell: CellRef<'a, R>| {
+            let (x, y, t) = cell.coord;
+            (((x + border) * (height + 2 * border) + y + border) * period + t) as usize
+        };
+        for x in 0..self.config.width {
+            for y in 0..self.config.height {
+                for t in 0..self.config.period {
+                    let cell = self.find_cell((x, y, t)).unwrap();
+                    if visited[index(cell)] {
+                        continue;
+                    }
+                    visited[index(cell)] = true;
+                    let mut stack = vec![cell];
+                    while let Some(cell) = stack.pop() {
+                        let is_rep = cell.coord == (x, y, t);
+                        unsafe {
+                            let cell_ptr = self.find_cell_mut(cell.coord).unwrap();
+                            cell_ptr.as_mut().unwrap().is_orbit_rep = is_rep;
+                        }
+                        for &sym in cell.sym.iter() {
+                            if !visited[index(sym)] {
+                                visited[index(sym)] = true;
+                                stack.push(sym);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Links each cell in the stator to the same cell in other generations,
+    /// in the same way as symmetric cells are linked.
+    ///
+    /// Cells outside the world are skipped here.
+    /// `init_known` returns an error for them.
+    fn init_stator(mut self) -> Self {
+        for &(x, y) in self.config.stator.clone().iter() {
+            if x < 0 || x >= self.config.width || y < 0 || y >= self.config.height {
+                continue;
+            }
+            for t in 0..self.config.period {
+                let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
+                for t1 in 0..self.config.period {
+                    if t1 != t {
+                        let cell = self.find_cell((x, y, t1)).unwrap();
+                        unsafe {
+                            let cell_ptr = cell_ptr.as_mut().unwrap();
+                            if !cell_ptr.sym.contains(&cell) {
+                                cell_ptr.sym.push(cell);
+                            }
+                        }
+                    }
                 }
             }
         }
         self
     }
 
+    /// Makes a deep copy of the world, which continues the search
+    /// in the same way as the original one.
+    ///
+    /// The cells cannot simply be copied, because they refer to each other.
+    /// So a new world is built from the configuration, and the cells in the
+    /// `set_stack` are set again in the same order, which takes about as
+    /// long as creating the world and replaying the stack.
+    ///
+    /// The progress callback is not copied, and the copy has its own
+    /// abort handle.
+    pub(crate) fn clone_world<'b>(&self) -> World<'b, R> {
+        // The same configuration has already passed the checks in `new`.
+        let mut world = World::new(&self.config, self.rule.clone()).unwrap();
+        for (cell, new_cell) in self.cells.iter().zip(world.cells.iter()) {
+            new_cell.activity.set(cell.activity.get());
+            new_cell.phase.set(cell.phase.get());
+        }
+        for set_cell in self.set_stack.iter() {
+            let new_cell = world.find_cell(set_cell.cell.coord).unwrap();
+            if new_cell.state.get().is_none() {
+                world.set_cell(
+                    new_cell,
+                    set_cell.cell.state.get().unwrap(),
+                    set_cell.reason,
+                );
+            }
+        }
+        world.conflicts = self.conflicts;
+        world.decisions = self.decisions;
+        world.propagations = self.propagations;
+        world.max_depth = self.max_depth;
+        world.max_decision_level = self.max_decision_level;
+        world.results = self.results;
+        world.restarts = self.restarts;
+        world.saved_phases = self.saved_phases;
+        world.next_restart = self.next_restart;
+        world.init_config = self.init_config.clone();
+        world.activity_inc = self.activity_inc;
+        world.activity_heap.rebuild(&world.search_list);
+        world.rng = self.rng.clone();
+        world.check_index = self.check_index;
+        world.search_index = self.search_index;
+        #[cfg(feature = "trace")]
+        {
+            world.trace = self.trace.clone();
+            world.trace_capacity = self.trace_capacity;
+        }
+        world
+    }
+
     /// Sets states for the cells.
     ///
     /// All cells are set to unknown unless they are on the boundary,
+    /// are outside the `bounding_box` in generation 0,
     /// or are marked as known in `init_pred_succ` or `init_sym`.
     fn init_state(mut self) -> Self {
+        let bounding_box = self.config.bounding_box;
+        let in_box = |x, y| match bounding_box {
+            Some((box_x, box_y, width, height)) => {
+                x >= box_x && x < box_x + width && y >= box_y && y < box_y + height
+            }
+            None => true,
+        };
         for x in 0..self.config.width {
             for y in 0..self.config.height {
                 for t in 0..self.config.period {
+                    if t == 0 && !in_box(x, y) {
+                        continue;
+                    }
                     let cell = self.find_cell((x, y, t)).unwrap();
                     if !self.set_stack.iter().any(|s| s.cell == cell) {
                         self.clear_cell(cell);
@@ -333,6 +679,154 @@ impl<'a, R: Rule> World<'a, R> {
         self
     }
 
+    /// Finds the cells in the `forbidden` pattern of the configuration.
+    ///
+    /// Cells outside the world are skipped here.
+    /// `init_known` returns an error for them.
+    fn init_forbidden(mut self) -> Self {
+        self.forbidden = self
+            .config
+            .forbidden
+            .iter()
+            .filter_map(|&(coord, state)| {
+                let cell = self.find_cell(self.config.translate(coord))?;
+                Some((cell, state))
+            })
+            .collect();
+        self
+    }
+
+    /// Sets the cells in `known_cells` of the configuration,
+    /// and deduces their consequences.
+    ///
+    /// Returns an error if a known cell is outside the world,
+    /// or if the known cells contradict each other, the rule,
+    /// or other parts of the configuration.
+    ///
+    /// Known cells that are symmetric to each other but have different
+    /// states are reported before setting any cell.
+    pub(crate) fn init_known(mut self) -> Result<Self, Error> {
+        self.set_known()?;
+        Ok(self)
+    }
+
+    /// Sets the cells in `known_cells` of the configuration,
+    /// and deduces their consequences.
+    ///
+    /// See `init_known` for details.
+    fn set_known(&mut self) -> Result<(), Error> {
+        for &(coord, _) in self.config.forbidden.iter() {
+            self.find_cell(self.config.translate(coord))
+                .ok_or(Error::SetCellError(coord))?;
+        }
+        for &(x, y) in self.config.stator.iter() {
+            if x < 0 || x >= self.config.width || y < 0 || y >= self.config.height {
+                return Err(Error::SetCellError((x, y, 0)));
+            }
+        }
+        let known_cells: HashMap<Coord, State> = self
+            .config
+            .known_cells
+            .iter()
+            .map(|&(coord, state)| (self.config.translate(coord), state))
+            .collect();
+        for &(coord, state) in self.config.known_cells.iter() {
+            let coord = self.config.translate(coord);
+            for sym_coord in self.config.symmetry_coords(coord) {
+                if matches!(known_cells.get(&sym_coord), Some(&sym_state) if sym_state != state) {
+                    return Err(Error::SymmetryKnownCellsError(coord, sym_coord));
+                }
+            }
+        }
+        for &(coord, state) in self.config.known_cells.clone().iter() {
+            let cell = self
+                .find_cell(self.config.translate(coord))
+                .ok_or(Error::SetCellError(coord))?;
+            if state.0 >= self.rule.gen() {
+                return Err(Error::SetCellError(coord));
+            }
+            match cell.state.get() {
+                Some(old_state) if old_state != state => return Err(Error::SetCellError(coord)),
+                Some(_) => (),
+                None => {
+                    if !self.set_cell(cell, state, Reason::Known) {
+                        return Err(Error::KnownCellsError);
+                    }
+                }
+            }
+        }
+        if self.check_forbidden() && self.proceed() {
+            Ok(())
+        } else {
+            Err(Error::KnownCellsError)
+        }
+    }
+
+    /// Resets the world to the state right after it was created from
+    /// `init_config`, without allocating the cells again.
+    ///
+    /// All the cells are cleared, the statistics, the activities and the
+    /// saved phases are reset, the random number generator is seeded
+    /// again, and then the cells fixed by the configuration
+    /// and the known cells are set again. The progress callback,
+    /// the abort handle and the capacity of the trace are kept.
+    pub(crate) fn reset(&mut self) -> Result<(), Error> {
+        self.config = self.init_config.clone();
+        while let Some(set_cell) = self.set_stack.pop() {
+            self.clear_cell(set_cell.cell);
+        }
+        for cell in self.cells.iter() {
+            cell.activity.set(0.0);
+            cell.phase.set(None);
+        }
+        self.conflicts = 0;
+        self.decisions = 0;
+        self.propagations = 0;
+        self.max_depth = 0;
+        self.decision_level = 0;
+        self.max_decision_level = 0;
+        self.results = 0;
+        self.restarts = 0;
+        self.saved_phases = 0;
+        self.next_restart = if self.config.restart {
+            Some(RESTART_UNIT)
+        } else {
+            None
+        };
+        self.activity_inc = 1.0;
+        self.activity_heap.rebuild(&self.search_list);
+        self.rng = Self::new_rng(&self.config);
+        self.check_index = 0;
+        self.search_index = 0;
+        self.abort.store(false, Ordering::Relaxed);
+        if !self.set_fixed() {
+            return Err(Error::KnownCellsError);
+        }
+        #[cfg(feature = "trace")]
+        self.trace.clear();
+        self.set_known()
+    }
+
+    /// Sets the cells that are fixed by the configuration (see `is_fixed`)
+    /// to the background, if they are unknown.
+    ///
+    /// Returns `false` if setting some cell causes a conflict,
+    /// e.g., with `non_empty_front` or `forbid_empty`.
+    pub(crate) fn set_fixed(&mut self) -> bool {
+        let mut result = true;
+        for x in 0..self.config.width {
+            for y in 0..self.config.height {
+                for t in 0..self.config.period {
+                    let cell = self.find_cell((x, y, t)).unwrap();
+                    if cell.state.get().is_none() && self.is_fixed(cell) {
+                        result &= self.set_cell(cell, cell.background, Reason::Deduce);
+                    }
+                }
+            }
+        }
+        result
+    }
+
     /// Sets the search order.
     fn init_search_order(mut self, search_order: SearchOrder) -> Self {
         match search_order {
@@ -340,7 +834,9 @@ impl<'a, R: Rule> World<'a, R> {
                 for x in 0..self.config.width {
                     for y in 0..self.config.height {
                         for t in 0..self.config.period {
-                            let cell = self.find_cell((x, y, t)).unwrap();
+                            let cell = self
+                                .find_cell(self.config.flip_to_start_corner((x, y, t)))
+                                .unwrap();
                             self.search_list.push(cell);
                         }
                     }
@@ -350,7 +846,9 @@ impl<'a, R: Rule> World<'a, R> {
                 for y in 0..self.config.height {
                     for x in 0..self.config.width {
                         for t in 0..self.config.period {
-                            let cell = self.find_cell((x, y, t)).unwrap();
+                            let cell = self
+                                .find_cell(self.config.flip_to_start_corner((x, y, t)))
+                                .unwrap();
                             self.search_list.push(cell);
                         }
                     }
@@ -361,7 +859,9 @@ impl<'a, R: Rule> World<'a, R> {
                 for i in 0..size {
                     for j in 0..=i {
                         for t in 0..self.config.period {
-                            let cell = self.find_cell((j, i - j, t)).unwrap();
+                            let cell = self
+                                .find_cell(self.config.flip_to_start_corner((j, i - j, t)))
+                                .unwrap();
                             self.search_list.push(cell);
                         }
                     }
@@ -369,27 +869,58 @@ impl<'a, R: Rule> World<'a, R> {
                 for i in 0..size {
                     for j in i + 1..size {
                         for t in 0..self.config.period {
-                            let cell = self.find_cell((j, size + i - j, t)).unwrap();
+                            let cell = self
+                                .find_cell(self.config.flip_to_start_corner((j, size + i - j, t)))
+                                .unwrap();
                             self.search_list.push(cell);
                         }
                     }
                 }
             }
         }
+        for pos in 0..self.search_list.len() {
+            let cell_ptr = self.find_cell_mut(self.search_list[pos].coord).unwrap();
+            unsafe {
+                cell_ptr.as_mut().unwrap().search_pos = Some(pos);
+            }
+        }
+        self.activity_heap = ActivityHeap::new(&self.search_list);
         self
     }
 
+    /// Whether the state of a cell is fixed by the configuration,
+    /// i.e., its predecessor or some symmetric cell is out of the
+    /// search range.
+    ///
+    /// Such cells are marked as known in `init_pred_succ` and `init_sym`.
+    pub(crate) fn is_fixed(&self, cell: CellRef<'a, R>) -> bool {
+        let (x, y, t) = cell.coord;
+        let (width, height) = (self.config.width, self.config.height);
+        (t == 0
+            && self
+                .find_cell(self.config.translate((x, y, t - 1)))
+                .is_none())
+            || self
+                .config
+                .symmetry_coords(cell.coord)
+                .into_iter()
+                .any(|(x, y, _)| x < 0 || x >= width || y < 0 || y >= height)
+    }
+
     /// Finds a cell by its coordinates. Returns a `CellRef`.
     pub(crate) fn find_cell(&self, coord: Coord) -> Option<CellRef<'a, R>> {
         let (x, y, t) = coord;
-        if x >= -1
-            && x <= self.config.width
-            && y >= -1
-            && y <= self.config.height
+        let border = self.border;
+        if x >= -border
+            && x < self.config.width + border
+            && y >= -border
+            && y < self.config.height + border
             && t >= 0
             && t < self.config.period
         {
-            let index = ((x + 1) * (self.config.height + 2) + y + 1) * self.config.period + t;
+            let index = ((x + border) * (self.config.height + 2 * border) + y + border)
+                * self.config.period
+                + t;
             let cell = &self.cells[index as usize];
             Some(cell.borrow())
         } else {
@@ -400,14 +931,17 @@ impl<'a, R: Rule> World<'a, R> {
     /// Finds a cell by its coordinates. Returns a mutable pointer.
     fn find_cell_mut(&mut self, coord: Coord) -> Option<*mut LifeCell<'a, R>> {
         let (x, y, t) = coord;
-        if x >= -1
-            && x <= self.config.width
-            && y >= -1
-            && y <= self.config.height
+        let border = self.border;
+        if x >= -border
+            && x < self.config.width + border
+            && y >= -border
+            && y < self.config.height + border
             && t >= 0
             && t < self.config.period
         {
-            let index = ((x + 1) * (self.config.height + 2) + y + 1) * self.config.period + t;
+            let index = ((x + border) * (self.config.height + 2 * border) + y + border)
+                * self.config.period
+                + t;
             Some(&mut self.cells[index as usize])
         } else {
             None
@@ -419,16 +953,24 @@ impl<'a, R: Rule> World<'a, R> {
     ///
     /// The original state of the cell must be unknown.
     ///
-    /// Return `false` if the number of living cells exceeds the `max_cell_count`
-    /// or the front becomes empty.
+    /// Return `false` if the number of living cells exceeds the `max_cell_count`,
+    /// or the front or generation 0 becomes empty.
     pub(crate) fn set_cell(&mut self, cell: CellRef<'a, R>, state: State, reason: Reason) -> bool {
         cell.state.set(Some(state));
         let mut result = true;
         cell.update_desc(Some(state), true);
         if state == !cell.background {
             self.cell_count[cell.coord.2 as usize] += 1;
+            if cell.is_orbit_rep {
+                self.orbit_cell_count[cell.coord.2 as usize] += 1;
+            }
             if let Some(max) = self.config.max_cell_count {
-                if self.cell_count() > max {
+                if self.limited_cell_count() > max {
+                    result = false;
+                }
+            }
+            if let Some(max) = self.config.max_cell_count_per_gen {
+                if self.limited_cell_count_gen(cell.coord.2 as usize) > max {
                     result = false;
                 }
             }
@@ -439,7 +981,19 @@ impl<'a, R: Rule> World<'a, R> {
                 result = false;
             }
         }
+        if cell.coord.2 == 0 && state == cell.background {
+            self.gen0_cell_count -= 1;
+            if self.config.forbid_empty && self.gen0_cell_count == 0 {
+                result = false;
+            }
+        }
+        if reason.is_decision() {
+            self.decision_level += 1;
+            self.max_decision_level = self.max_decision_level.max(self.decision_level);
+        }
         self.set_stack.push(SetCell::new(cell, reason));
+        #[cfg(feature = "trace")]
+        self.record(cell, Some(reason));
         result
     }
 
@@ -447,14 +1001,27 @@ impl<'a, R: Rule> World<'a, R> {
     /// and update the neighborhood descriptor of its neighbors.
     pub(crate) fn clear_cell(&mut self, cell: CellRef<'a, R>) {
         let old_state = cell.state.take();
-        if old_state != None {
+        if old_state.is_some() {
             cell.update_desc(old_state, false);
             if old_state == Some(!cell.background) {
                 self.cell_count[cell.coord.2 as usize] -= 1;
+                if cell.is_orbit_rep {
+                    self.orbit_cell_count[cell.coord.2 as usize] -= 1;
+                }
             }
             if cell.is_front && old_state == Some(cell.background) {
                 self.front_cell_count += 1;
             }
+            if cell.coord.2 == 0 && old_state == Some(cell.background) {
+                self.gen0_cell_count += 1;
+            }
+            if self.config.decision_heuristic == DecisionHeuristic::Vsids {
+                if let Some(pos) = cell.search_pos {
+                    self.activity_heap.insert(pos, &self.search_list);
+                }
+            }
+            #[cfg(feature = "trace")]
+            self.record(cell, None);
         }
     }
 
@@ -472,17 +1039,101 @@ impl<'a, R: Rule> World<'a, R> {
             })
     }
 
-    /// Tests whether the world is nonempty,
-    /// and whether the minimal period of the pattern equals to the given period.
+    /// Gets the unknown cell with the highest activity.
+    ///
+    /// Ties are broken by the order in the `search_list`.
+    pub(crate) fn get_most_active(&mut self) -> Option<(usize, CellRef<'a, R>)> {
+        let pos = self.activity_heap.peek_unknown(&self.search_list)?;
+        Some((pos, self.search_list[pos]))
+    }
+
+    /// Tests whether the pattern is nontrivial according to `triviality`
+    /// in the configuration.
+    ///
+    /// By default, tests whether the world is nonempty, and whether
+    /// the minimal period of the pattern equals to the given period.
     pub(crate) fn nontrivial(&self) -> bool {
-        self.cell_count[0] > 0
-            && (1..self.config.period).all(|t| {
+        let triviality = self.config.triviality;
+        self.cell_count[0] >= triviality.min_cell_count
+            && (!triviality.reject_subperiodic || self.is_full_period())
+    }
+
+    /// Tests whether the minimal period of the pattern equals to the given period.
+    ///
+    /// If `strict_period` is set, rotations, reflections and translations
+    /// are also taken into account. See `actual_period` for details.
+    fn is_full_period(&self) -> bool {
+        if self.config.strict_period {
+            self.actual_period() == self.config.period
+        } else {
+            (1..self.config.period).all(|t| {
                 self.config.period % t != 0
                     || self
                         .cells
                         .chunks(self.config.period as usize)
                         .any(|c| c[0].state.get() != c[t as usize].state.get())
             })
+        }
+    }
+
+    /// Cells in generation `t` whose states differ from the background,
+    /// sorted by their coordinates.
+    ///
+    /// Unknown cells are also included.
+    fn pattern(&self, t: isize) -> Vec<(Point, Option<State>)> {
+        let mut pattern = Vec::new();
+        for x in 0..self.config.width {
+            for y in 0..self.config.height {
+                let cell = self.find_cell((x, y, t)).unwrap();
+                let state = cell.state.get();
+                if state != Some(cell.background) {
+                    pattern.push(((x, y), state));
+                }
+            }
+        }
+        pattern
+    }
+
+    /// The actual period of the pattern, which divides the given period.
+    ///
+    /// It is the minimal `t` such that generation `t` can be obtained by
+    /// applying an isometry `S` to generation 0, where applying `S`
+    /// `period / t` times is the same as applying the transformation and
+    /// the translation defined in the configuration.
+    ///
+    /// Only meaningful when there are no unknown cells, e.g., after a result
+    /// is found. Returns the given period if generation 0 is empty.
+    pub fn actual_period(&self) -> isize {
+        let period = self.config.period;
+        let gen0 = self.pattern(0);
+        (1..period)
+            .filter(|t| period % t == 0)
+            .find(|&t| {
+                let gen_t = self.pattern(t);
+                !gen0.is_empty()
+                    && gen0.len() == gen_t.len()
+                    && ISOMETRIES.iter().any(|&f| {
+                        let mut image: Vec<_> = gen0.iter().map(|&(p, s)| (f(p), s)).collect();
+                        image.sort_unstable_by_key(|&(p, _)| p);
+                        let ((x0, y0), (xt, yt)) = (image[0].0, gen_t[0].0);
+                        let (dx, dy) = (xt - x0, yt - y0);
+                        let s = |p: Point| {
+                            let (x, y) = f(p);
+                            (x + dx, y + dy)
+                        };
+                        image
+                            .iter()
+                            .zip(gen_t.iter())
+                            .all(|(&(p, state), &(q, state_t))| {
+                                (p.0 + dx, p.1 + dy) == q && state == state_t
+                            })
+                            && [(0, 0), (1, 0), (0, 1)].iter().all(|&p| {
+                                let (x, y, _) = self.config.translate((p.0, p.1, -period));
+                                (0..period / t).fold(p, |p, _| s(p)) == (x, y)
+                            })
+                    })
+            })
+            .unwrap_or(period)
     }
 
     /// Gets the state of a cell. Returns `Err(())` if there is no such cell.
@@ -498,4 +1149,22 @@ impl<'a, R: Rule> World<'a, R> {
     pub(crate) fn cell_count(&self) -> usize {
         *self.cell_count.iter().min().unwrap()
     }
+
+    /// The number of living cells in generation `t` that counts
+    /// towards `max_cell_count_per_gen`, according to `count_mode`.
+    pub(crate) fn limited_cell_count_gen(&self, t: usize) -> usize {
+        match self.config.count_mode {
+            CountMode::All => self.cell_count[t],
+            CountMode::OrbitRepresentatives => self.orbit_cell_count[t],
+        }
+    }
+
+    /// The number of living cells that counts towards `max_cell_count`,
+    /// according to `count_mode`.
+    pub(crate) fn limited_cell_count(&self) -> usize {
+        match self.config.count_mode {
+            CountMode::All => self.cell_count(),
+            CountMode::OrbitRepresentatives => *self.orbit_cell_count.iter().min().unwrap(),
+        }
+    }
 }
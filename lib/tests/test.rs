@@ -1,4 +1,20 @@
-use rlifesrc_lib::{Config, Error, SearchOrder, Status, Symmetry, Transform};
+use rlifesrc_lib::{
+    rules::{validate_rule_string, Hrot, Life, LifeGen, NtLife, NtLifeGen, ParseRuleError},
+    Boundary, CellState, Config, Corner, CountMode, DecisionHeuristic, Error, NewState, NoneReason,
+    OutputFormat, SearchOrder, SetReason, State, Status, Symmetry, Transform, Triviality, ALIVE,
+    DEAD,
+};
+use std::{
+    cell::RefCell,
+    ops::ControlFlow,
+    rc::Rc,
+    sync::{atomic::Ordering, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "serialize")]
+use rlifesrc_lib::WorldSer;
 
 #[test]
 fn default() -> Result<(), Error> {
@@ -25,6 +41,146 @@ fn square_world() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn validate() {
+    assert_eq!(
+        Config::new(0, 5, 1).validate(),
+        Err(Error::NonPositiveError("width"))
+    );
+    assert_eq!(
+        Config::new(5, 4, 1)
+            .set_transform(Transform::Rotate90)
+            .validate(),
+        Err(Error::SquareWorldError("transform"))
+    );
+    assert_eq!(
+        Config::new(5, 4, 1).set_symmetry(Symmetry::C4).validate(),
+        Err(Error::SquareWorldError("symmetry"))
+    );
+    assert_eq!(
+        Config::new(5, 5, 1)
+            .set_new_state(NewState::RandomBiased(1.5))
+            .validate(),
+        Err(Error::ProbabilityError)
+    );
+    assert_eq!(
+        Config::new(5, 4, 1)
+            .set_search_order(Some(SearchOrder::Diagonal))
+            .validate(),
+        Err(Error::SquareWorldError("search_order"))
+    );
+    assert_eq!(
+        Config::new(5, 5, 4)
+            .set_translate(1, 0)
+            .set_symmetry(Symmetry::D2Col)
+            .validate(),
+        Err(Error::TranslateSymmetryError)
+    );
+    assert_eq!(
+        Config::new(5, 5, 4)
+            .set_translate(0, 1)
+            .set_symmetry(Symmetry::D2Col)
+            .validate(),
+        Ok(())
+    );
+    assert_eq!(
+        Config::new(5, 5, 2)
+            .set_translate(0, 1)
+            .set_transform(Transform::FlipRow)
+            .set_symmetry(Symmetry::D2Col)
+            .validate(),
+        Ok(())
+    );
+    assert_eq!(
+        Config::new(5, 5, 2)
+            .set_translate(1, 0)
+            .set_transform(Transform::FlipRow)
+            .set_symmetry(Symmetry::D2Col)
+            .validate(),
+        Err(Error::TranslateSymmetryError)
+    );
+    let config = Config::new(4, 4, 1)
+        .set_translate(isize::MAX / 2 + 1, 0)
+        .set_transform(Transform::FlipRow);
+    assert_eq!(config.validate(), Ok(()));
+    assert_eq!(
+        config.set_symmetry(Symmetry::D2Col).validate(),
+        Err(Error::TranslateSymmetryError)
+    );
+    assert_eq!(
+        Config::new(4, 4, 1)
+            .set_translate(isize::MIN, 0)
+            .set_transform(Transform::FlipAntidiag)
+            .set_symmetry(Symmetry::D2Antidiag)
+            .validate(),
+        Err(Error::TranslateSymmetryError)
+    );
+    assert_eq!(
+        Config::new(4, 4, 1)
+            .set_bounding_box(Some((1, 1, isize::MAX, 2)))
+            .validate(),
+        Err(Error::BoundingBoxError)
+    );
+}
+
+#[test]
+fn build() {
+    let config = Config::new(5, 5, 2).set_translate(1, 0);
+    assert_eq!(config.clone().build(), Ok(config));
+    let config = Config::new(5, 5, 2).set_rule_string("B3/S23|B36/S23");
+    assert_eq!(config.clone().build(), Ok(config));
+    assert_eq!(validate_rule_string("B3/S23|B36/S23"), Ok(()));
+    assert_eq!(validate_rule_string("R2,C2,S6-11,B9-11,NM"), Ok(()));
+    assert!(validate_rule_string("B3/S23|B36/S23x").is_err());
+    assert_eq!(
+        Config::new(0, 4, 0)
+            .set_symmetry(Symmetry::C4)
+            .set_rule_string("B3/S23x")
+            .build()
+            .map_err(|errors| errors.len()),
+        Err(4)
+    );
+    let errors = Config::new(0, 4, 0).build().unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            Error::NonPositiveError("width"),
+            Error::NonPositiveError("period")
+        ]
+    );
+}
+
+#[test]
+fn preset() -> Result<(), Error> {
+    for &name in Config::PRESETS.iter() {
+        let config = Config::preset(name).unwrap();
+        assert_eq!(config.validate(), Ok(()));
+        let mut search = config.world()?;
+        assert_eq!(search.search(None), Status::Found, "{}", name);
+    }
+    assert_eq!(Config::preset("p1 spaceship"), None);
+    Ok(())
+}
+
+#[test]
+fn display() {
+    assert_eq!(Status::Found.to_string(), "Found a result.");
+    assert_eq!(Status::None.to_string(), "No more result.");
+    assert_eq!(
+        Config::new(16, 16, 1).to_string(),
+        "16x16, period 1, rule B3/S23"
+    );
+    assert_eq!(
+        Config::new(12, 13, 3)
+            .set_translate(0, 1)
+            .set_transform(Transform::FlipCol)
+            .set_symmetry(Symmetry::D2Col)
+            .set_rule_string("B36/S23")
+            .to_string(),
+        "12x13, period 3, translate (0, 1), transform F|, symmetry D2|, rule B36/S23"
+    );
+}
+
 #[test]
 fn max_cell_count() -> Result<(), Error> {
     let config = Config::new(5, 5, 1).set_max_cell_count(Some(5));
@@ -35,6 +191,43 @@ fn max_cell_count() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn reset_max_cell_count() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_max_cell_count(Some(20));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    search.reset_max_cell_count(Some(30))?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!(search.cell_count() <= 30);
+    search.reset_max_cell_count(Some(10))?;
+    assert_eq!(search.search(None), Status::None);
+    Ok(())
+}
+
+#[test]
+fn max_decision_level() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let statistics = search.statistics();
+    assert!(statistics.decision_level > 0);
+    assert!(statistics.max_decision_level >= statistics.decision_level);
+    let rle = search.rle_gen(0);
+
+    let config = config.set_max_decision_level(Some(statistics.max_decision_level));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.rle_gen(0), rle);
+
+    let config = config.set_max_decision_level(Some(2));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    assert!(search.statistics().max_decision_level <= 2);
+    Ok(())
+}
+
 #[test]
 fn reduce_max() -> Result<(), Error> {
     let config = Config::new(5, 5, 1)
@@ -66,90 +259,1888 @@ fn p3_spaceship() -> Result<(), Error> {
 }
 
 #[test]
-fn lwss() -> Result<(), Error> {
-    let config = Config::new(6, 6, 4).set_translate(0, 2);
+fn rle() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
     let mut search = config.world()?;
     assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.rle(0),
+        String::from(
+            "x = 16, y = 5, rule = B3/S23\n\
+             8bo$b2ob3ob3o$b2o4bo2b2ob2o$o2bob2o3bo2b2o$12bo2bo!\n"
+        )
+    );
     Ok(())
 }
 
 #[test]
-fn lwss_flip() -> Result<(), Error> {
-    let config = Config::new(5, 5, 2)
-        .set_translate(0, 1)
-        .set_transform(Transform::FlipCol);
+fn write_rle() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
     let mut search = config.world()?;
     assert_eq!(search.search(None), Status::Found);
+    let mut buf = Vec::new();
+    for t in 0..3 {
+        search.write_rle(t, &mut buf).unwrap();
+    }
+    let rles: String = (0..3).map(|t| search.rle(t)).collect();
+    assert_eq!(String::from_utf8(buf).unwrap(), rles);
     Ok(())
 }
 
 #[test]
-fn turtle() -> Result<(), Error> {
-    let config = Config::new(12, 13, 3)
-        .set_translate(0, 1)
-        .set_symmetry(Symmetry::D2Col);
+fn rle_trimmed() -> Result<(), Error> {
+    let config = Config::new(3, 3, 2);
     let mut search = config.world()?;
+    assert_eq!(search.pattern_bounding_box(None), None);
+    assert_eq!(
+        search.rle_trimmed(0, false),
+        String::from("x = 0, y = 0, rule = B3/S23\n!\n")
+    );
     assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.pattern_bounding_box(Some(0)), Some((0, 1, 3, 1)));
+    assert_eq!(search.pattern_bounding_box(None), Some((0, 0, 3, 3)));
+    assert_eq!(
+        search.rle_trimmed(0, false),
+        String::from("x = 3, y = 1, rule = B3/S23\n3o!\n")
+    );
+    assert_eq!(
+        search.rle_trimmed(1, false),
+        String::from("x = 1, y = 3, rule = B3/S23\no$o$o!\n")
+    );
+    assert_eq!(search.rle_trimmed(1, true), search.rle(1));
     Ok(())
 }
 
 #[test]
-fn diagonal() -> Result<(), Error> {
-    let config = Config::new(8, 8, 4)
-        .set_translate(1, 1)
-        .set_search_order(Some(SearchOrder::Diagonal));
+fn export() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
     let mut search = config.world()?;
     assert_eq!(search.search(None), Status::Found);
+    for t in 0..3 {
+        assert_eq!(search.export(t, OutputFormat::Rle), search.rle(t));
+        assert_eq!(
+            search.export(t, OutputFormat::RleTrimmed),
+            search.rle_trimmed(t, false)
+        );
+        assert_eq!(
+            search.export(t, OutputFormat::Plaintext),
+            search.to_plaintext(t)
+        );
+        assert_eq!(
+            search.export(t, OutputFormat::Macrocell),
+            search.to_macrocell(t)
+        );
+    }
     Ok(())
 }
 
 #[test]
-fn b0() -> Result<(), Error> {
-    let config = Config::new(3, 3, 2).set_rule_string("B026/S1");
+fn lifehistory_rle() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
     let mut search = config.world()?;
     assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.lifehistory_rle(0, &[((8, 0, 0), 3), ((0, 0, 0), 4), ((1, 1, 1), 5)]),
+        String::from(
+            "x = 16, y = 5, rule = LifeHistory\n\
+             D7.C$.2A.3A.3A$.2A4.A2.2A.2A$A2.A.2A3.A2.2A$12.A2.A!\n"
+        )
+    );
     Ok(())
 }
 
 #[test]
-fn p3_2333() -> Result<(), Error> {
-    let config = Config::new(4, 4, 3).set_rule_string("23/3/3");
+fn rle_relative() -> Result<(), Error> {
+    let config = Config::new(5, 5, 2).set_rule_string("B03/S13");
     let mut search = config.world()?;
     assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.rle_gen_with(0, true), search.rle_gen(0));
+    let inverted: String = search
+        .rle_gen(1)
+        .lines()
+        .skip(1)
+        .flat_map(|line| line.chars().chain(Some('\n')))
+        .map(|c| match c {
+            'o' => '.',
+            '.' => 'o',
+            c => c,
+        })
+        .collect();
+    assert!(search.rle_gen_with(1, true).ends_with(&inverted));
+    assert_eq!(search.cell_state_relative((-1, -1, 1))?, CellState::Dead);
     Ok(())
 }
 
 #[test]
-fn snowflakes() -> Result<(), Error> {
-    let config = Config::new(4, 4, 2)
-        .set_translate(0, 1)
-        .set_rule_string("B2ci3ai4c8/S02ae3eijkq4iz5ar6i7e");
+fn plaintext() -> Result<(), Error> {
+    let config = Config::new(3, 3, 1);
     let mut search = config.world()?;
+    assert_eq!(
+        search.to_plaintext(0),
+        String::from(
+            "!Rule: B3/S23\n\
+             ???\n\
+             ???\n\
+             ???\n"
+        )
+    );
     assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.to_plaintext(0),
+        String::from(
+            "!Rule: B3/S23\n\
+             OO.\n\
+             OO.\n\
+             ...\n"
+        )
+    );
     Ok(())
 }
 
 #[test]
-#[cfg(feature = "serialize")]
-fn ser() -> Result<(), Error> {
+fn macrocell() -> Result<(), Error> {
+    let config = Config::new(3, 3, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.to_macrocell(0),
+        String::from(
+            "[M2] (rlifesrc)\n\
+             #R B3/S23\n\
+             **$**$\n"
+        )
+    );
+
     let config = Config::new(16, 5, 3).set_translate(0, 1);
     let mut search = config.world()?;
-    assert_eq!(search.search(Some(100)), Status::Searching);
-    let count = search.cell_count();
-    let save = search.ser();
-    let mut new_search = save.world()?;
-    assert_eq!(new_search.cell_count(), count);
-    assert_eq!(new_search.search(None), Status::Found);
+    assert_eq!(search.search(None), Status::Found);
     assert_eq!(
-        new_search.rle_gen(0),
+        search.to_macrocell(0),
         String::from(
-            "x = 16, y = 5, rule = B3/S23\n\
-             ........o.......$\n\
-             .oo.ooo.ooo.....$\n\
-             .oo....o..oo.oo.$\n\
-             o..o.oo...o..oo.$\n\
-             ............o..o!\n"
+            "[M2] (rlifesrc)\n\
+             #R B3/S23\n\
+             $.**.***$.**....*$*..*.**$\n\
+             *$***$..**.**$..*..**$....*..*$\n\
+             4 1 2 0 0\n"
         )
     );
     Ok(())
 }
+
+#[test]
+fn search_until() -> Result<(), Error> {
+    let config = Config::new(20, 20, 5).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search_until(Instant::now(), 100), Status::Searching);
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    let deadline = Instant::now() + Duration::from_secs(3600);
+    assert_eq!(search.search_until(deadline, 100), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn abort_handle() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    let abort = search.abort_handle();
+    abort.store(true, Ordering::Relaxed);
+    assert_eq!(search.search(None), Status::Paused);
+    assert!(!abort.load(Ordering::Relaxed));
+    let handle = thread::spawn(move || abort.store(true, Ordering::Relaxed));
+    handle.join().unwrap();
+    assert_eq!(search.search(None), Status::Paused);
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn search_with_callback() -> Result<(), Error> {
+    let config = Config::new(3, 3, 1);
+    let mut search = config.world()?;
+    let mut count = 0;
+    while search.search(None) == Status::Found {
+        count += 1;
+    }
+
+    let mut search = config.world()?;
+    let mut rles = Vec::new();
+    let status = search.search_with_callback(None, &mut |world| {
+        rles.push(world.rle_gen(0));
+        ControlFlow::Continue(())
+    });
+    assert_eq!(status, Status::None);
+    assert_eq!(rles.len(), count);
+
+    let mut search = config.world()?;
+    let mut first = None;
+    let status = search.search_with_callback(None, &mut |world| {
+        first = Some(world.rle_gen(0));
+        ControlFlow::Break(())
+    });
+    assert_eq!(status, Status::Found);
+    assert_eq!(first.as_ref(), rles.first());
+    assert_eq!(search.rle_gen(0), rles[0]);
+    Ok(())
+}
+
+#[test]
+fn statistics() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.statistics(), Default::default());
+    assert_eq!(search.search(None), Status::Found);
+    let stats = search.statistics();
+    assert_eq!(stats.conflicts, search.conflicts());
+    assert!(stats.decisions > 0);
+    assert!(stats.propagations >= stats.decisions);
+    assert!(stats.max_depth >= search.cell_count());
+    Ok(())
+}
+
+#[test]
+fn progress() -> Result<(), Error> {
+    let config = Config::new(8, 8, 2).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.progress(), 0.0);
+    let mut max_progress: f64 = 0.0;
+    while search.search(Some(100)) == Status::Searching {
+        let progress = search.progress();
+        assert!((0.0..=1.0).contains(&progress));
+        max_progress = max_progress.max(progress);
+    }
+    assert!(max_progress > 0.5);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "trace")]
+fn trace() -> Result<(), Error> {
+    use std::collections::HashMap;
+
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert!(search.trace().is_empty());
+    search.set_trace_capacity(1 << 20);
+    assert_eq!(search.search(None), Status::Found);
+    let trace = search.trace();
+    assert!(trace.iter().any(|event| event.state.is_none()));
+
+    // Replaying the trace gives the states of the result.
+    let mut states = HashMap::new();
+    for event in trace.iter() {
+        assert_eq!(event.state.is_some(), event.reason.is_some());
+        states.insert(event.coord, event.state);
+    }
+    for (&coord, &state) in states.iter() {
+        assert_eq!(search.get_cell_state(coord)?, state);
+    }
+
+    // Only the last events are kept.
+    search.set_trace_capacity(10);
+    assert!(search.trace().is_empty());
+    search.search(None);
+    assert_eq!(search.trace().len(), 10);
+    search.set_trace_capacity(0);
+    search.search(None);
+    assert!(search.trace().is_empty());
+
+    // Nothing is allocated until the changes are recorded.
+    search.set_trace_capacity(usize::MAX);
+    search.search(None);
+    assert!(!search.trace().is_empty());
+    Ok(())
+}
+
+#[test]
+fn boxed_clone() -> Result<(), Error> {
+    let configs = [
+        Config::new(16, 5, 3).set_translate(0, 1),
+        Config::new(16, 5, 3)
+            .set_translate(0, 1)
+            .set_decision_heuristic(DecisionHeuristic::Vsids)
+            .set_phase_saving(true)
+            .set_restart(true),
+        Config::new(16, 5, 3)
+            .set_translate(0, 1)
+            .set_new_state(NewState::Random)
+            .set_seed(Some(7))
+            .set_restart(true),
+    ];
+    for config in configs.iter() {
+        let mut search = config.world()?;
+        assert_eq!(search.search(Some(100)), Status::Searching);
+        let mut clone = search.boxed_clone();
+        assert_eq!(clone.statistics(), search.statistics());
+        assert_eq!(clone.rle(0), search.rle(0));
+        for _ in 0..3 {
+            assert_eq!(clone.search(Some(500)), search.search(Some(500)));
+            assert_eq!(clone.statistics(), search.statistics());
+            assert_eq!(clone.rle(0), search.rle(0));
+        }
+        for _ in 0..2 {
+            assert_eq!(search.search(None), Status::Found);
+            assert_eq!(clone.search(None), Status::Found);
+            assert_eq!(clone.statistics(), search.statistics());
+            assert_eq!(clone.rle(0), search.rle(0));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn restart() -> Result<(), Error> {
+    let config = Config::new(8, 8, 2)
+        .set_translate(0, 1)
+        .set_new_state(NewState::Random)
+        .set_restart(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    assert!(search.statistics().restarts > 0);
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_new_state(NewState::Random)
+        .set_restart(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn random_biased() -> Result<(), Error> {
+    // In this rule every pattern is a still life,
+    // so every cell is decided without any deduction or conflict.
+    for &p in [0.2, 0.7].iter() {
+        let config = Config::new(40, 40, 1)
+            .set_rule_string("B/S012345678")
+            .set_non_empty_front(false)
+            .set_new_state(NewState::RandomBiased(p))
+            .set_seed(Some(42));
+        let mut search = config.world()?;
+        assert_eq!(search.search(None), Status::Found);
+        assert_eq!(search.conflicts(), 0);
+        let fraction = search.cell_count() as f64 / 1600.0;
+        assert!((fraction - p).abs() < 0.06, "{} {}", p, fraction);
+
+        // The same seed gives the same result.
+        let mut other = config.world()?;
+        assert_eq!(other.search(None), Status::Found);
+        assert_eq!(other.cell_states_gen(0), search.cell_states_gen(0));
+        search.reset()?;
+        assert_eq!(search.search(None), Status::Found);
+        assert_eq!(other.cell_states_gen(0), search.cell_states_gen(0));
+    }
+    Ok(())
+}
+
+#[test]
+fn seeded_random() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_new_state(NewState::RandomBiased(0.3))
+        .set_seed(Some(2021));
+    let mut first = config.world()?;
+    let mut second = config.world()?;
+    assert_eq!(first.search(Some(300)), second.search(Some(300)));
+    let mut clone = first.boxed_clone();
+    assert_eq!(first.search(None), Status::Found);
+    assert_eq!(second.search(None), Status::Found);
+    assert_eq!(clone.search(None), Status::Found);
+    assert_eq!(second.statistics(), first.statistics());
+    assert_eq!(clone.statistics(), first.statistics());
+    assert_eq!(second.rle(0), first.rle(0));
+    assert_eq!(clone.rle(0), first.rle(0));
+    Ok(())
+}
+
+#[test]
+fn vsids() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_decision_heuristic(DecisionHeuristic::Vsids);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let config = Config::new(5, 5, 3).set_decision_heuristic(DecisionHeuristic::Vsids);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    Ok(())
+}
+
+#[test]
+fn phase_saving() -> Result<(), Error> {
+    let config = Config::new(10, 10, 3).set_symmetry(Symmetry::D8);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let conflicts = search.conflicts();
+    assert_eq!(search.statistics().saved_phases, 0);
+    let config = config.set_phase_saving(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!(search.conflicts() <= conflicts);
+    assert!(search.statistics().saved_phases > 0);
+    Ok(())
+}
+
+#[test]
+fn search_parallel() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let (status, search) = config.search_parallel(4, None)?;
+    assert_eq!(status, Status::Found);
+    let search = search.unwrap();
+    assert!(search.cell_count() > 0);
+    assert_eq!(
+        search.get_cell_state((0, 0, 0))?,
+        search.get_cell_state((0, 1, 3))?
+    );
+    let config = Config::new(5, 5, 3);
+    let (status, search) = config.search_parallel(4, None)?;
+    assert_eq!(status, Status::None);
+    assert!(search.is_none());
+    let config = Config::new(20, 20, 4).set_rule_string("B3/S23/C3");
+    let (status, _) = config.search_parallel(2, Some(100))?;
+    assert_eq!(status, Status::Searching);
+    Ok(())
+}
+
+#[test]
+fn front_lines() -> Result<(), Error> {
+    // The first column is empty.
+    let known_cells = (0..4).map(|y| ((0, y, 0), DEAD)).collect();
+    let config = Config::new(10, 4, 2).set_known_cells(known_cells);
+    assert!(matches!(config.world(), Err(Error::KnownCellsError)));
+
+    let config = config.set_front_lines(2);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!((0..4).any(|y| search.get_cell_state((1, y, 0)) == Ok(Some(ALIVE))));
+
+    assert_eq!(
+        config.set_front_lines(0).validate(),
+        Err(Error::NonPositiveError("front_lines"))
+    );
+    Ok(())
+}
+
+#[test]
+fn max_cell_count_per_gen() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_max_cell_count_per_gen(Some(25));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!((0..3).all(|t| search.cell_count_gen(t) <= 25));
+    let config = config.set_max_cell_count_per_gen(Some(24));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    Ok(())
+}
+
+#[test]
+fn world_size() {
+    let config = Config::new(isize::MAX, isize::MAX, 2);
+    assert_eq!(config.validate(), Err(Error::WorldSizeError));
+    assert_eq!(config.world().err(), Some(Error::WorldSizeError));
+
+    // The cell count fits, but not the border around the world.
+    let config = Config::new(isize::MAX, 1, 1);
+    assert_eq!(config.validate(), Ok(()));
+    assert_eq!(config.world().err(), Some(Error::WorldSizeError));
+
+    let config = Config::new(4, 4, 1).set_translate(isize::MAX, 0);
+    assert_eq!(config.world().err(), Some(Error::WorldSizeError));
+
+    let config = Config::new(16, 16, 2).set_max_cells(Some(511));
+    assert_eq!(config.world().err(), Some(Error::WorldSizeError));
+    let config = config.set_max_cells(Some(512));
+    assert!(config.world().is_ok());
+}
+
+#[test]
+fn count_mode() -> Result<(), Error> {
+    let config = Config::new(6, 6, 1)
+        .set_symmetry(Symmetry::D8)
+        .set_non_empty_front(false)
+        .set_max_cell_count(Some(1));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+
+    // The pond and the block in the middle are single orbits.
+    let config = config.set_count_mode(CountMode::OrbitRepresentatives);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.cell_count(), 8);
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.cell_count(), 4);
+    assert_eq!(search.search(None), Status::None);
+
+    let config = config.set_max_cell_count(Some(2)).set_reduce_max(true);
+    let mut search = config.world()?;
+    while search.search(None) == Status::Found {}
+    assert_eq!(search.config().max_cell_count, Some(0));
+    Ok(())
+}
+
+#[test]
+fn bounding_box() -> Result<(), Error> {
+    let config = Config::new(18, 5, 3)
+        .set_translate(0, 1)
+        .set_non_empty_front(false)
+        .set_bounding_box(Some((1, 0, 16, 5)));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    for y in 0..5 {
+        assert_eq!(search.get_cell_state((0, y, 0))?, Some(DEAD));
+        assert_eq!(search.get_cell_state((17, y, 0))?, Some(DEAD));
+    }
+    let config = Config::new(16, 16, 1).set_bounding_box(Some((4, 4, 3, 3)));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.cell_count(), 4);
+    let config = config.set_bounding_box(Some((4, 4, 1, 3)));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    let config = config.set_bounding_box(Some((12, 0, 5, 5)));
+    assert_eq!(config.world().err(), Some(Error::BoundingBoxError));
+    let config = config.set_bounding_box(Some((0, 0, 0, 5)));
+    assert_eq!(
+        config.world().err(),
+        Some(Error::NonPositiveError("bounding_box"))
+    );
+    Ok(())
+}
+
+#[test]
+fn forbidden() -> Result<(), Error> {
+    let block = vec![
+        ((4, 4, 0), ALIVE),
+        ((5, 4, 0), ALIVE),
+        ((4, 5, 0), ALIVE),
+        ((5, 5, 0), ALIVE),
+    ];
+    let config = Config::new(16, 16, 1)
+        .set_bounding_box(Some((4, 4, 3, 3)))
+        .set_forbidden(block.clone());
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let states = block
+        .iter()
+        .map(|&(coord, _)| search.get_cell_state(coord))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(states.iter().any(|&state| state != Some(ALIVE)));
+    let config = config.set_bounding_box(Some((4, 4, 2, 2)));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    Ok(())
+}
+
+#[test]
+fn triviality() -> Result<(), Error> {
+    fn results(config: &Config) -> Result<Vec<(usize, bool)>, Error> {
+        let mut search = config.world()?;
+        let mut results = Vec::new();
+        while search.search(None) == Status::Found {
+            let still = search.cell_states_gen(0) == search.cell_states_gen(1);
+            results.push((search.cell_count_gen(0), still));
+        }
+        Ok(results)
+    }
+
+    let config = Config::new(4, 4, 2).set_non_empty_front(false);
+    let default = results(&config)?;
+    assert!(!default.is_empty());
+    assert!(default.iter().all(|&(count, still)| count > 0 && !still));
+
+    let config = config.set_triviality(Triviality {
+        min_cell_count: 4,
+        ..Triviality::default()
+    });
+    let large = results(&config)?;
+    assert!(large.iter().all(|&(count, _)| count >= 4));
+    assert!(large.len() < default.len());
+
+    let config = config.set_triviality(Triviality {
+        min_cell_count: 0,
+        reject_subperiodic: false,
+    });
+    let all = results(&config)?;
+    assert!(all.contains(&(0, true)));
+    assert!(all.contains(&(4, true)));
+    assert_eq!(
+        all.iter().filter(|&&(_, still)| !still).count(),
+        default.len()
+    );
+
+    // The empty pattern is the smallest, so `reduce_max` stops there.
+    let config = Config::new(3, 3, 1)
+        .set_non_empty_front(false)
+        .set_reduce_max(true)
+        .set_triviality(Triviality {
+            min_cell_count: 0,
+            reject_subperiodic: false,
+        });
+    let mut search = config.world()?;
+    let mut last = None;
+    while search.search(None) == Status::Found {
+        last = Some(search.cell_count());
+    }
+    assert_eq!(last, Some(0));
+    assert_eq!(search.search(None), Status::None);
+    Ok(())
+}
+
+#[test]
+fn forbid_empty() -> Result<(), Error> {
+    // The only still life in a 2x2 world with a dead boundary
+    // is the block, but it is ruled out by the bounding box.
+    let config = Config::new(2, 2, 1)
+        .set_bounding_box(Some((0, 0, 2, 1)))
+        .set_non_empty_front(false)
+        .set_triviality(Triviality {
+            min_cell_count: 0,
+            reject_subperiodic: false,
+        });
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.cell_count_gen(0), 0);
+    assert_eq!(search.search(None), Status::None);
+
+    let config = config.set_forbid_empty(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    Ok(())
+}
+
+#[test]
+fn symmetric_known_cells() -> Result<(), Error> {
+    let config = Config::new(6, 6, 2)
+        .set_symmetry(Symmetry::C2)
+        .set_known_cells(vec![((1, 2, 0), ALIVE)]);
+    let search = config.world()?;
+    assert_eq!(search.get_cell_state((4, 3, 0))?, Some(ALIVE));
+
+    let config = config.set_known_cells(vec![((1, 2, 0), ALIVE), ((4, 3, 0), DEAD)]);
+    assert_eq!(
+        config.world().err(),
+        Some(Error::SymmetryKnownCellsError((1, 2, 0), (4, 3, 0)))
+    );
+    Ok(())
+}
+
+#[test]
+fn torus() -> Result<(), Error> {
+    // A blinker that crosses the left and right edges.
+    let config = Config::new(6, 6, 2)
+        .set_boundary(Boundary::Torus)
+        .set_known_cells(vec![
+            ((5, 2, 0), ALIVE),
+            ((0, 2, 0), ALIVE),
+            ((1, 2, 0), ALIVE),
+        ])
+        .set_max_cell_count(Some(3));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    for y in 1..4 {
+        assert_eq!(search.get_cell_state((0, y, 1))?, Some(ALIVE));
+    }
+    let config = config.set_boundary(Boundary::Dead);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+
+    let config = Config::new(6, 6, 2)
+        .set_translate(0, 1)
+        .set_boundary(Boundary::Torus);
+    assert_eq!(config.validate(), Err(Error::TorusTranslateError));
+    Ok(())
+}
+
+#[test]
+fn symmetry_offset() -> Result<(), Error> {
+    // An agar with glide symmetry: reflected across the middle row,
+    // then shifted by half the width.
+    let config = Config::new(8, 4, 2)
+        .set_boundary(Boundary::Torus)
+        .set_symmetry(Symmetry::D2Row)
+        .set_symmetry_offset(4, 0)
+        .set_non_empty_front(false);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let mut mirror_symmetric = true;
+    for t in 0..2 {
+        for x in 0..8 {
+            for y in 0..4 {
+                let state = search.get_cell_state((x, y, t))?;
+                assert_eq!(search.get_cell_state(((x + 4) % 8, 3 - y, t))?, state);
+                if search.get_cell_state((x, 3 - y, t))? != state {
+                    mirror_symmetric = false;
+                }
+            }
+        }
+    }
+    assert!(!mirror_symmetric);
+
+    let config = config.set_boundary(Boundary::Dead);
+    assert_eq!(config.validate(), Err(Error::SymmetryOffsetError));
+    let config = Config::new(8, 4, 2)
+        .set_boundary(Boundary::Torus)
+        .set_symmetry_offset(4, 0);
+    assert_eq!(config.validate(), Err(Error::SymmetryOffsetError));
+    Ok(())
+}
+
+#[test]
+fn stator() -> Result<(), Error> {
+    let config = Config::new(5, 5, 2)
+        .set_known_cells(vec![((2, 1, 0), ALIVE), ((2, 2, 0), ALIVE)])
+        .set_stator(vec![(2, 2)]);
+    let mut search = config.world()?;
+    assert_eq!(search.get_cell_state((2, 2, 1))?, Some(ALIVE));
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.get_cell_state((2, 2, 1))?, Some(ALIVE));
+
+    let mut stator = Vec::new();
+    for x in 0..5 {
+        for y in 0..5 {
+            stator.push((x, y));
+        }
+    }
+    let config = Config::new(5, 5, 2)
+        .set_strict_period(true)
+        .set_stator(stator);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+
+    let config = Config::new(5, 5, 2).set_stator(vec![(5, 2)]);
+    assert_eq!(config.world().err(), Some(Error::SetCellError((5, 2, 0))));
+    Ok(())
+}
+
+#[test]
+fn none_reason() -> Result<(), Error> {
+    let config = Config::new(5, 5, 3);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    assert_eq!(search.none_reason(), NoneReason::NotExist);
+    let config = Config::new(3, 3, 1);
+    let mut search = config.world()?;
+    while search.search(None) == Status::Found {}
+    assert_eq!(search.none_reason(), NoneReason::NoMore);
+    assert!(search.statistics().results > 1);
+    let config = Config::new(4, 4, 1).set_reduce_max(true);
+    let mut search = config.world()?;
+    while search.search(None) == Status::Found {}
+    assert_eq!(search.none_reason(), NoneReason::NoSmaller);
+    Ok(())
+}
+
+#[test]
+fn cell_reason() -> Result<(), Error> {
+    let config = Config::new(5, 5, 2).set_known_cells(vec![((2, 2, 0), ALIVE)]);
+    let mut search = config.world()?;
+    assert_eq!(search.cell_reason((2, 2, 0))?, Some(SetReason::Known));
+    assert_eq!(search.cell_reason((-1, -1, 0))?, Some(SetReason::Init));
+    assert_eq!(search.cell_reason((0, 0, 1))?, None);
+    assert_eq!(
+        search.cell_reason((8, 8, 0)),
+        Err(Error::GetCellError((8, 8, 0)))
+    );
+    assert_eq!(search.search(None), Status::Found);
+    let mut reasons = Vec::new();
+    for x in 0..5 {
+        for y in 0..5 {
+            for t in 0..2 {
+                let reason = search.cell_reason((x, y, t))?;
+                assert!(reason.is_some());
+                reasons.push(reason.unwrap());
+            }
+        }
+    }
+    assert!(reasons.contains(&SetReason::Assume));
+    assert!(reasons.contains(&SetReason::Deduce));
+    Ok(())
+}
+
+#[test]
+fn cell_count_gen() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let counts: Vec<_> = (0..3).map(|t| search.cell_count_gen(t)).collect();
+    assert_eq!(counts.iter().min(), Some(&search.cell_count()));
+    assert_eq!(search.cell_count_gen(3), counts[0]);
+    assert_eq!(search.cell_count_gen(-1), counts[2]);
+    assert_eq!(search.population_profile(), counts);
+    assert_eq!(Some(&search.max_population()), counts.iter().max());
+    Ok(())
+}
+
+#[test]
+fn cell_state() -> Result<(), Error> {
+    let config = Config::new(6, 6, 3).set_rule_string("B2/S/3");
+    let mut search = config.world()?;
+    assert_eq!(search.cell_state((2, 2, 0))?, CellState::Unknown);
+    assert!(search.cell_state((8, 8, 0)).is_err());
+    assert_eq!(search.search(None), Status::Found);
+    let mut dying = 0;
+    for x in 0..6 {
+        for y in 0..6 {
+            for t in 0..3 {
+                let state = search.cell_state((x, y, t))?;
+                assert_eq!(state, CellState::from(search.get_cell_state((x, y, t))?));
+                if let CellState::Dying(_) = state {
+                    dying += 1;
+                }
+            }
+        }
+    }
+    assert!(dying > 0);
+    let states = search.cell_states_gen(1);
+    assert_eq!((states.len(), states[0].len()), (6, 6));
+    assert_eq!(states[2][3], search.cell_state((3, 2, 1))?);
+    for &state in [Some(DEAD), Some(ALIVE), Some(State(2)), None].iter() {
+        assert_eq!(Option::<State>::from(CellState::from(state)), state);
+    }
+    assert_eq!(CellState::from(Some(State(2))), CellState::Dying(2));
+    Ok(())
+}
+
+#[test]
+fn cells() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.cells(0).count(), 80);
+    assert!(search.cells(0).all(|(_, state)| state.is_none()));
+    assert_eq!(search.search(None), Status::Found);
+    let cells: Vec<_> = search.cells(1).collect();
+    assert_eq!(cells[0].0, (0, 0, 1));
+    assert_eq!(cells[17], ((1, 1, 1), search.get_cell_state((1, 1, 1))?));
+    let alive = cells.iter().filter(|&&(_, state)| state == Some(ALIVE));
+    assert_eq!(alive.count(), search.cell_count_gen(1));
+    Ok(())
+}
+
+#[test]
+fn dimensions() -> Result<(), Error> {
+    let search = Config::new(16, 5, 3).world()?;
+    assert_eq!(search.dimensions(), (16, 5, 3));
+    Ok(())
+}
+
+#[test]
+fn progress_callback() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let calls_clone = calls.clone();
+    search.set_progress_callback(
+        100,
+        Box::new(move |statistics| calls_clone.borrow_mut().push(statistics.conflicts)),
+    );
+    assert_eq!(search.search(None), Status::Found);
+    let calls = calls.borrow();
+    assert_eq!(calls.len() as u64, search.conflicts() / 100);
+    assert!(calls
+        .iter()
+        .enumerate()
+        .all(|(i, &n)| n == 100 * (i as u64 + 1)));
+    Ok(())
+}
+
+#[test]
+fn strict_period() -> Result<(), Error> {
+    let config = Config::new(5, 5, 8).set_translate(2, 2);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    // A glider becomes a reflected copy of itself after 2 generations.
+    assert_eq!(search.actual_period(), 2);
+    let config = config.set_strict_period(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    let config = Config::new(5, 5, 2)
+        .set_translate(0, 1)
+        .set_transform(Transform::FlipCol)
+        .set_strict_period(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.actual_period(), 2);
+    Ok(())
+}
+
+#[test]
+fn known_cells() -> Result<(), Error> {
+    let config = Config::new(5, 5, 2).set_known_cells(vec![
+        ((1, 2, 0), ALIVE),
+        ((2, 2, 0), ALIVE),
+        ((3, 2, 0), ALIVE),
+    ]);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.get_cell_state((1, 2, 0))?, Some(ALIVE));
+    while search.search(None) == Status::Found {}
+    assert_eq!(search.get_cell_state((1, 2, 0))?, Some(ALIVE));
+
+    let config = Config::new(3, 3, 1).set_known_cells(vec![((3, 3, 0), ALIVE)]);
+    assert_eq!(config.world().err(), Some(Error::SetCellError((3, 3, 0))));
+    let config = Config::new(3, 3, 1).set_known_cells(vec![((1, 1, 0), ALIVE), ((1, 1, 0), DEAD)]);
+    assert_eq!(config.world().err(), Some(Error::SetCellError((1, 1, 0))));
+    let mut known_cells = vec![((1, 1, 0), ALIVE)];
+    for x in 0..3 {
+        for y in 0..3 {
+            if (x, y) != (1, 1) {
+                known_cells.push(((x, y, 0), DEAD));
+            }
+        }
+    }
+    let config = Config::new(3, 3, 1).set_known_cells(known_cells);
+    assert_eq!(config.world().err(), Some(Error::KnownCellsError));
+    Ok(())
+}
+
+#[test]
+fn try_set_cell() -> Result<(), Error> {
+    let config = Config::new(5, 5, 2);
+    let mut search = config.world()?;
+    assert_eq!(search.get_cell((2, 2, 0)), None);
+    assert_eq!(search.get_cell((8, 8, 0)), None);
+    assert_eq!(search.search(None), Status::Found);
+    for x in 1..4 {
+        search.try_set_cell((x, 2, 0), ALIVE)?;
+    }
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.get_cell((2, 2, 0)), Some(ALIVE));
+    while search.search(None) == Status::Found {}
+    assert_eq!(search.get_cell((1, 2, 0)), Some(ALIVE));
+    assert_eq!(search.config().known_cells.len(), 3);
+
+    let mut search = Config::new(3, 3, 1).world()?;
+    assert_eq!(
+        search.try_set_cell((3, 3, 0), ALIVE),
+        Err(Error::KnownCellsError)
+    );
+    assert_eq!(
+        search.try_set_cell((8, 8, 0), ALIVE),
+        Err(Error::SetCellError((8, 8, 0)))
+    );
+    assert_eq!(
+        search.try_set_cell((1, 1, 0), State(2)),
+        Err(Error::SetCellError((1, 1, 0)))
+    );
+    search.try_set_cell((1, 1, 0), ALIVE)?;
+    search.try_set_cell((1, 1, 0), ALIVE)?;
+    assert_eq!(
+        search.try_set_cell((1, 1, 0), DEAD),
+        Err(Error::KnownCellsError)
+    );
+    for x in 0..3 {
+        search.try_set_cell((x, 0, 0), DEAD)?;
+    }
+    assert_eq!(
+        search.try_set_cell((0, 1, 0), DEAD),
+        Err(Error::KnownCellsError)
+    );
+    assert_eq!(search.get_cell((0, 1, 0)), None);
+    assert_eq!(search.config().known_cells.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn from_rle() -> Result<(), Error> {
+    let mut config = Config::from_rle(
+        "#N Partial spaceship\n\
+         x = 16, y = 5, rule = B3/S23\n\
+         8bo$b2ob3ob3o$b2o4bo2b2o!",
+    )
+    .unwrap()
+    .set_translate(0, 1);
+    config.period = 3;
+    assert_eq!(config.width, 16);
+    assert_eq!(config.height, 5);
+    assert_eq!(config.known_cells.len(), 14);
+    assert_eq!(config.known_cells[0], ((8, 0, 0), ALIVE));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.rle(0),
+        String::from(
+            "x = 16, y = 5, rule = B3/S23\n\
+             8bo$b2ob3ob3o$b2o4bo2b2ob2o$o2bob2o3bo2b2o$12bo2bo!\n"
+        )
+    );
+    assert!(Config::from_rle("y = 3\n3o!").is_err());
+    assert!(Config::from_rle("x = 3, y = 3\n3z!").is_err());
+    assert!(Config::from_rle("x = 3, y = 3\n4o!").is_err());
+    assert!(Config::from_rle("x = 3, y = 3\n3$o!").is_err());
+    assert!(Config::from_rle("x = 3, y = 3\n99999999999o!").is_err());
+    assert!(Config::from_rle("x = 3, y = 3\n99999999999999999999999b!").is_err());
+    assert!(Config::from_rle("x = 3, y = 3\n2$3o!").is_ok());
+    Ok(())
+}
+
+#[test]
+fn parse_transform_symmetry() {
+    assert_eq!("Id".parse(), Ok(Transform::Id));
+    assert_eq!(" id ".parse(), Ok(Transform::Id));
+    assert_eq!("I".parse(), Ok(Transform::Id));
+    assert_eq!("r90".parse(), Ok(Transform::Rotate90));
+    assert_eq!("f|".parse(), Ok(Transform::FlipCol));
+    assert!("R45".parse::<Transform>().is_err());
+    assert_eq!(
+        format!("{:?}", " r270".parse::<Transform>().unwrap()),
+        "R270"
+    );
+    assert_eq!("d2|".parse(), Ok(Symmetry::D2Col));
+    assert_eq!(" D4x\n".parse(), Ok(Symmetry::D4Diag));
+    assert_eq!("c4".parse(), Ok(Symmetry::C4));
+    assert!("D3".parse::<Symmetry>().is_err());
+    assert_eq!(format!("{:?}", "d4x".parse::<Symmetry>().unwrap()), "D4X");
+}
+
+#[test]
+fn transform_apply() {
+    let images: Vec<_> = [
+        Transform::Id,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipRow,
+        Transform::FlipCol,
+        Transform::FlipDiag,
+        Transform::FlipAntidiag,
+    ]
+    .iter()
+    .map(|transform| transform.apply((0, 1, 2), 4, 4))
+    .collect();
+    assert_eq!(
+        images,
+        vec![
+            (0, 1, 2),
+            (2, 0, 2),
+            (3, 2, 2),
+            (1, 3, 2),
+            (0, 2, 2),
+            (3, 1, 2),
+            (1, 0, 2),
+            (2, 3, 2),
+        ]
+    );
+    assert_eq!(Transform::Rotate180.apply((0, 0, 0), 3, 2), (2, 1, 0));
+}
+
+#[test]
+fn transform_mul() {
+    let transforms = [
+        Transform::Id,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipRow,
+        Transform::FlipCol,
+        Transform::FlipDiag,
+        Transform::FlipAntidiag,
+    ];
+    for &a in transforms.iter() {
+        assert_eq!(a * Transform::Id, a);
+        assert_eq!(Transform::Id * a, a);
+        assert_eq!(a * a.inverse(), Transform::Id);
+        assert_eq!(a.inverse() * a, Transform::Id);
+        for &b in transforms.iter() {
+            let coord = (0, 1, 0);
+            assert_eq!(
+                (a * b).apply(coord, 4, 4),
+                b.apply(a.apply(coord, 4, 4), 4, 4)
+            );
+            for &c in transforms.iter() {
+                assert_eq!((a * b) * c, a * (b * c));
+            }
+        }
+    }
+    assert_eq!(
+        Transform::Rotate90 * Transform::Rotate90,
+        Transform::Rotate180
+    );
+    assert_eq!(
+        Transform::Rotate90 * Transform::Rotate180,
+        Transform::Rotate270
+    );
+    assert_eq!(
+        Transform::FlipRow * Transform::FlipCol,
+        Transform::Rotate180
+    );
+    assert_eq!(
+        Transform::FlipDiag * Transform::FlipAntidiag,
+        Transform::Rotate180
+    );
+    assert_eq!(
+        Transform::FlipRow * Transform::FlipDiag,
+        Transform::Rotate90
+    );
+    assert_eq!(
+        Transform::FlipDiag * Transform::FlipRow,
+        Transform::Rotate270
+    );
+}
+
+#[test]
+fn symmetry_coords() {
+    assert_eq!(Symmetry::C1.coords((0, 1, 0), 4, 4), vec![]);
+    assert_eq!(Symmetry::C2.coords((0, 1, 0), 4, 4), vec![(3, 2, 0)]);
+    assert_eq!(
+        Symmetry::D4Ortho.coords((0, 1, 0), 4, 4),
+        vec![(3, 1, 0), (0, 2, 0), (3, 2, 0)]
+    );
+    assert_eq!(
+        Symmetry::D8.coords((0, 1, 0), 4, 4),
+        vec![
+            (1, 3, 0),
+            (2, 0, 0),
+            (3, 1, 0),
+            (0, 2, 0),
+            (1, 0, 0),
+            (2, 3, 0),
+            (3, 2, 0),
+        ]
+    );
+    assert_eq!(Symmetry::D2Diag.coords((1, 1, 0), 4, 4), vec![(1, 1, 0)]);
+}
+
+#[test]
+fn lwss() -> Result<(), Error> {
+    let config = Config::new(6, 6, 4).set_translate(0, 2);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn lwss_flip() -> Result<(), Error> {
+    let config = Config::new(5, 5, 2)
+        .set_translate(0, 1)
+        .set_transform(Transform::FlipCol);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn turtle() -> Result<(), Error> {
+    let config = Config::new(12, 13, 3)
+        .set_translate(0, 1)
+        .set_symmetry(Symmetry::D2Col);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn diagonal() -> Result<(), Error> {
+    let config = Config::new(8, 8, 4)
+        .set_translate(1, 1)
+        .set_search_order(Some(SearchOrder::Diagonal));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn auto_diagonal() -> Result<(), Error> {
+    let config = Config::new(6, 6, 4).set_translate(1, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search_order(), SearchOrder::Diagonal);
+    assert_eq!(search.search(None), Status::Found);
+    let conflicts = search.conflicts();
+
+    let config = config.set_search_order(Some(SearchOrder::RowFirst));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!(conflicts < search.conflicts());
+    Ok(())
+}
+
+#[test]
+fn auto_search_order() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    assert_eq!(config.auto_search_order(), SearchOrder::ColumnFirst);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let pattern = search.rle(0);
+
+    let config = config.set_search_order(Some(search.search_order()));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.rle(0), pattern);
+
+    let config = Config::new(5, 16, 3).set_translate(1, 0);
+    assert_eq!(config.auto_search_order(), SearchOrder::RowFirst);
+    Ok(())
+}
+
+#[test]
+fn search_increasing() -> Result<(), Error> {
+    let config = Config::new(1, 1, 2);
+    assert!(config.search_increasing(2, 1, None)?.world.is_none());
+    let result = config.search_increasing(6, 1, None)?;
+    assert!(result.timed_out.is_empty());
+    let search = result.world.unwrap();
+    assert_eq!(search.dimensions(), (3, 3, 2));
+    assert_eq!(search.cell_count(), 3);
+
+    let config = Config::new(4, 4, 3)
+        .set_translate(0, 1)
+        .set_symmetry(Symmetry::D2Col);
+    let result = config.search_increasing(8, 2, Some(100000))?;
+    assert!(result.world.is_none());
+    assert!(result.timed_out.is_empty());
+
+    // The glider in 4x4 is skipped with a tiny step limit.
+    let config = Config::new(3, 3, 4).set_translate(1, 1);
+    let search = config.search_increasing(5, 1, None)?.world.unwrap();
+    assert_eq!(search.dimensions(), (4, 4, 4));
+    let result = config.search_increasing(5, 1, Some(2))?;
+    assert!(result.world.is_none());
+    assert!(result.timed_out.contains(&(4, 4)));
+    Ok(())
+}
+
+#[test]
+fn start_corner() -> Result<(), Error> {
+    let config = Config::new(6, 6, 2)
+        .set_search_order(Some(SearchOrder::RowFirst))
+        .set_non_empty_front(false)
+        .set_strict_period(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let states = search.cell_states_gen(0);
+
+    let config = config.set_start_corner(Corner::TopRight);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let mirrored: Vec<Vec<_>> = search
+        .cell_states_gen(0)
+        .into_iter()
+        .map(|row| row.into_iter().rev().collect())
+        .collect();
+    assert_eq!(mirrored, states);
+    Ok(())
+}
+
+#[test]
+fn b0() -> Result<(), Error> {
+    let config = Config::new(3, 3, 2).set_rule_string("B026/S1");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn p3_2333() -> Result<(), Error> {
+    let config = Config::new(4, 4, 3).set_rule_string("23/3/3");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn snowflakes() -> Result<(), Error> {
+    let config = Config::new(4, 4, 2)
+        .set_translate(0, 1)
+        .set_rule_string("B2ci3ai4c8/S02ae3eijkq4iz5ar6i7e");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn map_rule() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1).set_rule_string(
+        "MAPARYXfhZofugWaH7oaIDogBZofuhogOiAaIDogIAAgAAWaH7oaIDogGiA6ICAAIAAaIDogIAAgACAAIAAAAAAAA",
+    );
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let config = Config::new(16, 5, 3).set_translate(0, 1).set_rule_string(
+        "MAPARYXfhZofugWaH7oaIDogBZofuhogOiAaIDogIAAgAAWaH7oaIDogGiA6ICAAIAAaIDogIAAgACAAIA",
+    );
+    assert_eq!(
+        config.world().err().map(|e| e.to_string()),
+        Some(String::from("Invalid rule: Invalid length for MAP rule"))
+    );
+    Ok(())
+}
+
+#[test]
+fn hex_rule() -> Result<(), Error> {
+    let config = Config::new(6, 6, 4)
+        .set_translate(0, 2)
+        .set_rule_string("B2o/S2m34H");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let config = Config::new(6, 6, 3)
+        .set_translate(1, 2)
+        .set_rule_string("B2/S2H");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn von_neumann_rule() -> Result<(), Error> {
+    let config = Config::new(5, 5, 2).set_rule_string("B2/SV");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.rle(0),
+        String::from(
+            "x = 5, y = 5, rule = B2/SV\n\
+             ob2o$4bo$obo$o$bo!\n"
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn hrot_rule() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut life = config.world()?;
+    assert_eq!(life.search(None), Status::Found);
+    for rule_string in &["R1,C2,M0,S2-3,B3,NM", "R1,C0,M1,S3..4,B3,NM"] {
+        let mut search = config.clone().set_rule_string(rule_string).world()?;
+        assert_eq!(search.search(None), Status::Found);
+        assert_eq!(search.cell_states_gen(0), life.cell_states_gen(0));
+    }
+
+    let config = Config::new(8, 8, 1).set_rule_string("R2,C2,S6-11,B9-11,NM");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.rle(0),
+        String::from(
+            "x = 8, y = 8, rule = R2,C2,S6-11,B9-11,NM\n\
+             3o2b3o$3o3b2o$3o3b2o$obo4bo$o$3o2b3o$3o3b2o$3o3b2o!\n"
+        )
+    );
+
+    assert!(matches!(
+        config
+            .clone()
+            .set_rule_string("R2,C2,S6-11,B9-11,NN")
+            .world(),
+        Err(Error::HrotError(_))
+    ));
+    assert!(matches!(
+        config
+            .clone()
+            .set_rule_string("R2,C3,S6-11,B9-11,NM")
+            .world(),
+        Err(Error::HrotError(_))
+    ));
+    assert_eq!(
+        "R2,C2,Q3,S6-11".parse::<Hrot>().err(),
+        Some(Error::RuleSyntaxError(6, ParseRuleError::Unexpected('Q')))
+    );
+    assert_eq!(
+        config.set_rule_string("R2,S6-x,B9-11").world().err(),
+        Some(Error::RuleSyntaxError(3, ParseRuleError::MissingNumber))
+    );
+    assert!("R1,C2,S0-99999999999,B3,NM".parse::<Hrot>().is_ok());
+    Ok(())
+}
+
+#[test]
+fn rule_display() -> Result<(), Error> {
+    assert_eq!("S23/B3".parse::<Life>()?.to_string(), "B3/S23");
+    assert_eq!("23/3".parse::<Life>()?.to_string(), "B3/S23");
+    assert_eq!("S23/B3".parse::<NtLife>()?.to_string(), "B3/S23");
+    assert_eq!("23/3/3".parse::<LifeGen>()?.to_string(), "B3/S23/C3");
+    assert_eq!(
+        "g4b2c36k7s2ak34-a5-i".parse::<NtLifeGen>()?.to_string(),
+        "B2c36k7/S2ka34-a5-i/C4"
+    );
+    let rule = "B2ci3ai4c8/S02ae3eijkq4iz5ar6i7e".parse::<NtLife>()?;
+    let rule_string = rule.to_string();
+    assert_eq!(rule_string.parse::<NtLife>()?.to_string(), rule_string);
+    assert_eq!(
+        "r2,m1,s2,3,5-7,b9..11".parse::<Hrot>()?.to_string(),
+        "R2,C2,M1,S2-3,5-7,B9-11,NM"
+    );
+    let rule_string = "B2/S34H".parse::<NtLife>()?.to_string();
+    assert!(rule_string.starts_with("MAP"));
+    assert_eq!(rule_string.parse::<NtLife>()?.to_string(), rule_string);
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_rule_string("S23/B3");
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    Ok(())
+}
+
+#[test]
+fn rule_variants() -> Result<(), Error> {
+    for &rule_string in [
+        "B3/S23", "b3s23", "23/3", "S23/B3", "s23b3", "B3,S23", "S23,B3",
+    ]
+    .iter()
+    {
+        assert_eq!(rule_string.parse::<Life>()?.to_string(), "B3/S23");
+        assert_eq!(rule_string.parse::<NtLife>()?.to_string(), "B3/S23");
+    }
+    for &rule_string in ["B3/S23/C3", "23/3/3", "B3,S23,C3", "23,3,3", "S23B3/C3"].iter() {
+        assert_eq!(rule_string.parse::<LifeGen>()?.to_string(), "B3/S23/C3");
+    }
+    assert_eq!("s2-a3b3".parse::<NtLife>()?.to_string(), "B3/S2-a3");
+    assert!("S23".parse::<Life>().is_err());
+    Ok(())
+}
+
+#[test]
+fn rule_error_position() {
+    assert_eq!(
+        "B3/S2q".parse::<NtLife>().err(),
+        Some(Error::RuleSyntaxError(5, ParseRuleError::ExtraJunk))
+    );
+    assert_eq!(
+        "B2q/S23".parse::<NtLife>().err(),
+        Some(Error::RuleSyntaxError(2, ParseRuleError::Missing('S')))
+    );
+    assert_eq!(
+        "B3/S23/Cx".parse::<NtLifeGen>().err(),
+        Some(Error::RuleSyntaxError(8, ParseRuleError::MissingNumber))
+    );
+    assert_eq!(
+        "B3/S23x".parse::<Life>().err(),
+        Some(Error::RuleSyntaxError(6, ParseRuleError::ExtraJunk))
+    );
+    assert_eq!(
+        validate_rule_string("B3/S23|B3/S2q"),
+        Err(Error::RuleSyntaxError(12, ParseRuleError::ExtraJunk))
+    );
+    assert_eq!(
+        Config::new(5, 5, 1)
+            .set_rule_string("B3/S2q")
+            .world()
+            .err()
+            .map(|e| e.to_string()),
+        Some(String::from(
+            "Invalid rule at position 5: Extra unparsed junk at the end of the rule string"
+        ))
+    );
+}
+
+#[test]
+fn world_with_rule() -> Result<(), Error> {
+    let rule = "B36/S23".parse::<Life>()?;
+    for &(width, height, period, dx) in [(3, 3, 1, 0), (5, 5, 2, 0), (16, 5, 3, 1)].iter() {
+        let config = Config::new(width, height, period)
+            .set_translate(0, dx)
+            .set_rule_string("B36/S23");
+        let mut search = config.world()?;
+        let mut new_search = config.world_with_rule(rule.clone())?;
+        assert_eq!(new_search.search(None), search.search(None));
+        assert_eq!(new_search.rle(0), search.rle(0));
+    }
+    let config = Config::new(0, 5, 1);
+    assert_eq!(
+        config.world_with_rule(rule).err(),
+        Some(Error::NonPositiveError("width"))
+    );
+    Ok(())
+}
+
+#[test]
+fn checkerboard() -> Result<(), Error> {
+    let rule = "B3/S23|b36s23".parse::<Life>()?;
+    assert_eq!(rule.to_string(), "B3/S23|B36/S23");
+    let rule = "B3/S23|B2-a/S23".parse::<NtLife>()?;
+    assert_eq!(rule.to_string(), "B3/S23|B2-a/S23");
+    assert_eq!(
+        "B0/S2|B3/S23".parse::<Life>().err(),
+        Some(Error::CheckerboardB0Error)
+    );
+    assert_eq!(
+        "B3/S23|B3/S23|B3/S23".parse::<Life>().err(),
+        Some(Error::CheckerboardNestedError)
+    );
+
+    fn results(config: &Config) -> Result<Vec<Vec<(isize, isize)>>, Error> {
+        let mut search = config.world()?;
+        let mut results = Vec::new();
+        while search.search(None) == Status::Found {
+            let mut cells = Vec::new();
+            for x in 0..config.width {
+                for y in 0..config.height {
+                    if search.get_cell_state((x, y, 0))? == Some(ALIVE) {
+                        cells.push((x, y));
+                    }
+                }
+            }
+            results.push(cells);
+        }
+        Ok(results)
+    }
+
+    // The same rule on both colors.
+    let config = Config::new(5, 5, 1);
+    let life = results(&config)?;
+    let config = config.set_rule_string("B3/S23|B3/S23");
+    assert_eq!(results(&config)?, life);
+
+    // Living cells with odd `x + y` always die.
+    let config = config.set_rule_string("B3/S23|B3/S");
+    let still_lifes = results(&config)?;
+    assert!(!still_lifes.is_empty());
+    assert!(still_lifes.len() < life.len());
+    assert!(still_lifes
+        .iter()
+        .all(|cells| cells.iter().all(|&(x, y)| (x + y) % 2 == 0)));
+    assert!(still_lifes.contains(&vec![(0, 2), (1, 1), (1, 3), (2, 2)]));
+
+    // Configurations that swap the two colors are rejected.
+    let config = Config::new(6, 6, 2).set_rule_string("B3/S23|B36/S23");
+    let swapped = [
+        config.clone().set_translate(0, 1),
+        config.clone().set_transform(Transform::FlipRow),
+        config.clone().set_symmetry(Symmetry::D2Row),
+        Config::new(5, 5, 2)
+            .set_rule_string("B3/S23|B36/S23")
+            .set_boundary(Boundary::Torus),
+    ];
+    for config in swapped.iter() {
+        assert_eq!(config.world().err(), Some(Error::CheckerboardColorError));
+        assert!(config.clone().set_rule_string("B3/S23").world().is_ok());
+    }
+    let preserved = [
+        config.clone().set_translate(1, 1),
+        config.clone().set_transform(Transform::Rotate180),
+        config.clone().set_symmetry(Symmetry::D2Diag),
+        config.clone().set_boundary(Boundary::Torus),
+    ];
+    for config in preserved.iter() {
+        assert!(config.world().is_ok());
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn ser() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(Some(100)), Status::Searching);
+    let count = search.cell_count();
+    let save = search.ser();
+    let mut new_search = save.world()?;
+    assert_eq!(new_search.cell_count(), count);
+    assert_eq!(new_search.statistics(), search.statistics());
+    assert_eq!(new_search.search(None), Status::Found);
+    assert_eq!(
+        new_search.rle_gen(0),
+        String::from(
+            "x = 16, y = 5, rule = B3/S23\n\
+             ........o.......$\n\
+             .oo.ooo.ooo.....$\n\
+             .oo....o..oo.oo.$\n\
+             o..o.oo...o..oo.$\n\
+             ............o..o!\n"
+        )
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn sweep_rules() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let rules: Vec<String> = vec!["B3/S23".into(), "B3/S2".into(), "Q3/S23".into()];
+    let results = config.sweep_rules(&rules, Some(100000));
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].0, "B3/S23");
+    let (status, save) = results[0].1.clone()?;
+    assert_eq!(status, Status::Found);
+    let search = save.unwrap().world()?;
+    assert_eq!(search.config().rule_string, "B3/S23");
+    let mut expected = config.world()?;
+    expected.search(None);
+    assert_eq!(search.rle(0), expected.rle(0));
+    assert!(matches!(results[1].1, Ok((Status::None, None))));
+    assert!(results[2].1.is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn save_version() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(Some(100)), Status::Searching);
+    let mut json = serde_json::to_value(search.ser()).unwrap();
+    assert_eq!(json["version"], 5);
+    json.as_object_mut().unwrap().remove("version");
+    json.as_object_mut().unwrap().remove("decisions");
+    let save: WorldSer = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(save.world()?.cell_count(), search.cell_count());
+    json["version"] = 100.into();
+    let save: WorldSer = serde_json::from_value(json).unwrap();
+    assert_eq!(save.world().err(), Some(Error::IncompatibleVersion(100)));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn load_world_size() -> Result<(), Error> {
+    let search = Config::new(4, 4, 1).world()?;
+    let mut json = serde_json::to_value(search.ser()).unwrap();
+    json["config"]["width"] = isize::MAX.into();
+    json["config"]["height"] = 1.into();
+    json["set_stack"] = serde_json::Value::Array(Vec::new());
+    let save: WorldSer = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(save.world().err(), Some(Error::WorldSizeError));
+    json["config"]["width"] = 4.into();
+    json["config"]["dx"] = 5.into();
+    let save: WorldSer = serde_json::from_value(json).unwrap();
+    assert_eq!(save.world().err(), Some(Error::SpeedOfLightError));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn resume() -> Result<(), Error> {
+    let config = Config::new(6, 6, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let first = search.rle_gen(0);
+    let mut new_search = search.ser().world()?;
+    assert_eq!(new_search.rle_gen(0), first);
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(new_search.resume(None), Status::Found);
+    assert_ne!(new_search.rle_gen(0), first);
+    assert_eq!(new_search.rle_gen(0), search.rle_gen(0));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "compressed")]
+fn compressed() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(Some(100)), Status::Searching);
+    let data = search.ser_compressed();
+    assert!(data.len() < serde_json::to_vec(&search.ser()).unwrap().len());
+    let save = WorldSer::from_compressed(&data)?;
+    assert_eq!(save, search.ser());
+    assert_eq!(save.world()?.cell_count(), search.cell_count());
+    assert!(WorldSer::from_compressed(&data[..data.len() / 2]).is_err());
+
+    // The version is checked before decoding.
+    let mut newer = data.clone();
+    newer[..4].copy_from_slice(&100u32.to_le_bytes());
+    assert_eq!(
+        WorldSer::from_compressed(&newer).err(),
+        Some(Error::IncompatibleVersion(100))
+    );
+    assert!(matches!(
+        WorldSer::from_compressed(&data[4..]),
+        Err(Error::CompressedSaveError(_))
+    ));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "apgcode")]
+fn apgcode() -> Result<(), Error> {
+    let config = Config::new(3, 3, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.apgcode(), None);
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.apgcode(), Some(String::from("xs4_33")));
+
+    let config = Config::new(3, 3, 2);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.apgcode(), Some(String::from("xp2_7")));
+
+    let config = Config::new(4, 4, 4).set_translate(1, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.apgcode(), Some(String::from("xq4_153")));
+
+    let config = Config::new(5, 5, 2)
+        .set_translate(0, 1)
+        .set_transform(Transform::FlipCol);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.apgcode(), Some(String::from("xq4_6frc")));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn results() -> Result<(), Error> {
+    let config = Config::new(3, 3, 1);
+    let mut search = config.world()?;
+    let mut results = search.results();
+    let mut patterns = Vec::new();
+    for save in &mut results {
+        let new_search = save.world()?;
+        let pattern = new_search.rle(0);
+        assert!(!patterns.contains(&pattern));
+        patterns.push(pattern);
+    }
+    assert_eq!(results.next(), None);
+    assert_eq!(patterns.len(), 9);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn results_deduplicated() -> Result<(), Error> {
+    // Block, ship, boat and tub.
+    let config = Config::new(3, 3, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.results_deduplicated().count(), 4);
+
+    // 26 results, but only 5 different oscillators.
+    let config = Config::new(5, 5, 2);
+    let mut search = config.world()?;
+    assert_eq!(search.results().count(), 26);
+    let mut search = config.world()?;
+    let results = search
+        .results_deduplicated()
+        .map(|save| Ok(save.world()?.rle(0)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    assert_eq!(results.len(), 5);
+    assert!(results.contains(&String::from("x = 5, y = 5, rule = B3/S23\n$3o!\n")));
+    Ok(())
+}
+
+#[test]
+fn config_hash() {
+    use std::collections::HashMap;
+
+    let config = Config::new(16, 16, 3).set_new_state(NewState::RandomBiased(0.25));
+    let mut memo = HashMap::new();
+    memo.insert(config.clone(), 1);
+    memo.insert(config.clone().set_symmetry(Symmetry::D2Row), 2);
+    memo.insert(config.clone().set_new_state(NewState::RandomBiased(0.5)), 3);
+    assert_eq!(memo.len(), 3);
+    assert_eq!(
+        memo.get(&Config::new(16, 16, 3).set_new_state(NewState::RandomBiased(0.25))),
+        Some(&1)
+    );
+    assert_eq!(memo.get(&Config::new(16, 16, 3)), None);
+    assert_ne!(NewState::RandomBiased(0.0), NewState::RandomBiased(-0.0));
+    assert_ne!(NewState::Random, NewState::RandomBiased(0.5));
+}
+
+#[test]
+fn max_partial() -> Result<(), Error> {
+    let config = Config::new(4, 4, 1).set_known_cells(vec![((1, 1, 0), ALIVE), ((0, 0, 0), DEAD)]);
+    let search = config.world()?;
+    let partial = search.rle_gen(0);
+    assert!(partial.contains('?'));
+    let alive = search.max_partial(0, true);
+    let dead = search.max_partial(0, false);
+    for ((p, a), d) in partial.chars().zip(alive.chars()).zip(dead.chars()) {
+        match p {
+            '?' => assert_eq!((a, d), ('o', '.')),
+            _ => assert_eq!((a, d), (p, p)),
+        }
+    }
+    let cells = search.max_partial_cells(0, true);
+    assert_eq!(cells[0][0], CellState::Dead);
+    assert_eq!(cells[1][1], CellState::Alive);
+    assert!(cells.iter().flatten().all(|&s| s != CellState::Unknown));
+    Ok(())
+}
+
+#[test]
+fn search_timed() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    let start = Instant::now();
+    let (status, stats, elapsed) = search.search_timed(None);
+    assert_eq!(status, Status::Found);
+    assert_eq!(stats, search.statistics());
+    assert!(stats.decisions > 0);
+    assert!(elapsed <= start.elapsed());
+    Ok(())
+}
+
+#[test]
+fn oblique() -> Result<(), Error> {
+    // No (2,1)c/6 knightships fit in a 6x6 box. The search is started from
+    // the corner that the pattern moves away from.
+    for &(dx, dy, corner) in &[
+        (2, 1, Corner::TopLeft),
+        (1, 2, Corner::TopLeft),
+        (-2, 1, Corner::TopRight),
+        (-1, -2, Corner::BottomRight),
+    ] {
+        let config = Config::new(6, 6, 6)
+            .set_translate(dx, dy)
+            .set_start_corner(corner);
+        let mut search = config.world()?;
+        assert_eq!(search.search(None), Status::None);
+    }
+    let config = Config::new(6, 6, 2).set_translate(3, 1);
+    assert!(matches!(config.world(), Err(Error::SpeedOfLightError)));
+    let config = Config::new(6, 6, 2).set_translate(2, -2);
+    assert!(config.world().is_ok());
+    Ok(())
+}
+
+#[test]
+fn reset() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let (coord, _) = search
+        .cells(0)
+        .find(|&(_, state)| state == Some(ALIVE))
+        .unwrap();
+    let config = config.set_known_cells(vec![(coord, ALIVE)]);
+    let mut fresh = config.world()?;
+    assert_eq!(fresh.search(None), Status::Found);
+
+    let mut search = config.world()?;
+    let abort = search.abort_handle();
+    assert_eq!(search.search(None), Status::Found);
+    search.search(Some(1000));
+    search.reset()?;
+    assert_eq!(search.statistics(), config.world()?.statistics());
+    assert_eq!(search.rle_gen(0), config.world()?.rle_gen(0));
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.rle_gen(0), fresh.rle_gen(0));
+    assert_eq!(search.statistics(), fresh.statistics());
+    assert!(Arc::ptr_eq(&abort, &search.abort_handle()));
+    Ok(())
+}
+
+#[test]
+fn reset_after_end() -> Result<(), Error> {
+    // All the results, and the final status.
+    fn run(search: &mut dyn rlifesrc_lib::Search) -> (Vec<String>, Status) {
+        let mut results = Vec::new();
+        loop {
+            match search.search(None) {
+                Status::Found => results.push(search.rle_gen(0)),
+                status => return (results, status),
+            }
+        }
+    }
+
+    let config = Config::new(6, 6, 4).set_translate(0, 2);
+    let mut search = config.world()?;
+    let expected = run(config.world()?.as_mut());
+    assert_eq!(run(search.as_mut()), expected);
+    search.reset()?;
+    assert_eq!(run(search.as_mut()), expected);
+
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_reduce_max(true);
+    let mut search = config.world()?;
+    let expected = run(config.world()?.as_mut());
+    assert!(!expected.0.is_empty());
+    assert_eq!(run(search.as_mut()), expected);
+    search.reset()?;
+    assert_eq!(search.config(), &config);
+    assert_eq!(run(search.as_mut()), expected);
+    Ok(())
+}
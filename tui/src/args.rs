@@ -1,7 +1,9 @@
 //! Parsing command-line arguments.
 
 use clap::{App, AppSettings, Arg, Error, ErrorKind, Result as ClapResult};
-use rlifesrc_lib::{rules::NtLifeGen, Config, NewState, Search, SearchOrder, Symmetry, Transform};
+use rlifesrc_lib::{
+    rules::validate_rule_string, Config, NewState, Search, SearchOrder, Symmetry, Transform,
+};
 
 fn is_positive(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_digit()) && s != "0" && !s.starts_with('-')
@@ -111,6 +113,7 @@ impl Args {
                     .long("transform")
                     .takes_value(true)
                     .possible_values(&["Id", "R90", "R180", "R270", "F|", "F-", "F\\", "F/"])
+                    .case_insensitive(true)
                     .default_value("Id"),
             )
             .arg(
@@ -129,6 +132,7 @@ impl Args {
                     .possible_values(&[
                         "C1", "C2", "C4", "D2|", "D2-", "D2\\", "D2/", "D4+", "D4X", "D8",
                     ])
+                    .case_insensitive(true)
                     .default_value("C1"),
             )
             .arg(
@@ -137,17 +141,16 @@ impl Args {
                     .long_help(
                         "Rule of the cellular automaton\n\
                          Supports Life-like, isotropic non-totalistic, hexagonal, MAP rules, \
-                         and their corresponding Generations rules.\n",
+                         and their corresponding Generations rules.\n\
+                         Also supports HROT rules with the Moore neighborhood, \
+                         e.g., R2,C2,S6-11,B9-11,NM.\n\
+                         Also supports checkerboard rules, e.g., B3/S23|B36/S23.\n",
                     )
                     .short("r")
                     .long("rule")
                     .takes_value(true)
                     .default_value("B3/S23")
-                    .validator(|d| {
-                        d.parse::<NtLifeGen>()
-                            .map(|_| ())
-                            .map_err(|e| e.to_string())
-                    }),
+                    .validator(|d| validate_rule_string(&d).map_err(|e| e.to_string())),
             )
             .arg(
                 Arg::with_name("ORDER")
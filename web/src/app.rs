@@ -1,11 +1,11 @@
 use crate::{
     help::Help,
     settings::Settings,
-    worker::{Request, Response, Worker},
-    world::World,
+    worker::{Explanation, Request, Response, Worker},
+    world::{Paint, World},
 };
-use js_sys::Array;
-use rlifesrc_lib::{Config, Status};
+use js_sys::{Array, Function, Reflect};
+use rlifesrc_lib::{Config, OutputFormat, SetReason, State, Status, ALIVE, DEAD};
 use std::time::Duration;
 use wasm_bindgen::JsValue;
 use web_sys::{Blob, BlobPropertyBag, FileList, HtmlAnchorElement, HtmlElement, Url};
@@ -17,9 +17,11 @@ use yew::{
     services::{
         interval::{IntervalService, IntervalTask},
         reader::{FileData, ReaderService, ReaderTask},
+        storage::{Area, StorageService},
+        timeout::{TimeoutService, TimeoutTask},
         DialogService,
     },
-    Bridge, Bridged, Component, ComponentLink, Html, ShouldRender,
+    Bridge, Bridged, Callback, Component, ComponentLink, Html, ShouldRender,
 };
 
 const INIT_WORLD: &str = "x = 16, y = 16, rule = B3/S23\n\
@@ -40,23 +42,55 @@ const INIT_WORLD: &str = "x = 16, y = 16, rule = B3/S23\n\
                           ????????????????$\n\
                           ????????????????!";
 
+/// Key of the refresh interval in the local storage.
+const REFRESH_KEY: &str = "rlifesrc.refresh";
+
+/// Default refresh interval in milliseconds, i.e., 60 fps.
+const DEFAULT_REFRESH: u64 = 1000 / 60;
+
 pub struct App {
     link: ComponentLink<Self>,
     config: Config,
     status: Status,
     gen: isize,
     cells: usize,
+    conflicts: u64,
+    progress: f64,
+    /// The index of the displayed result and the number of results found.
+    results: (Option<usize>, usize),
     world: String,
     max_partial: bool,
+    /// Whether to start the search again after a result is found.
+    auto_continue: bool,
+    /// Whether clicking a cell in the world changes its state.
+    paint: bool,
+    /// The cell under the mouse, its state, and why it has this state.
+    explanation: Option<Explanation>,
+    /// Whether the user has paused the search.
+    ///
+    /// A result may arrive after the user pressed `Pause`; the search
+    /// should not continue in this case.
+    paused: bool,
+    /// Refresh interval of the display in milliseconds while searching.
+    ///
+    /// `None` means that the display is only updated manually.
+    refresh: Option<u64>,
+    /// The format of the copied generation.
+    format: OutputFormat,
     worker: Box<dyn Bridge<Worker>>,
-    interval_task: Option<IntervalTask>,
+    /// Cycles through the generations when playing.
+    play_task: Option<IntervalTask>,
     reader_task: Option<ReaderTask>,
+    copied_task: Option<TimeoutTask>,
 }
 
 pub enum Msg {
     Tick,
     IncGen,
     DecGen,
+    Play,
+    Stop,
+    NextFrame,
     Start,
     Pause,
     Reset,
@@ -64,22 +98,41 @@ pub enum Msg {
     Load(FileList),
     SendFile(FileData),
     SetMaxPartial,
+    SetAutoContinue,
+    SetPaint,
+    Paint(Paint),
+    Explain((isize, isize)),
+    SetRefresh(Option<u64>),
+    SetFormat(OutputFormat),
+    Copy,
+    HideCopied,
+    PrevResult,
+    NextResult,
     Apply(Config),
     DataReceived(Response),
     None,
 }
 
 impl App {
-    fn start_job(&mut self) {
+    /// The last generation that can be displayed.
+    ///
+    /// This is `0` even if the period of a malformed configuration is not positive.
+    fn last_gen(&self) -> isize {
+        self.config.period.max(1) - 1
+    }
+
+    fn start_play(&mut self) {
+        // Plays at 1 fps when the display is only updated manually.
+        let refresh = self.refresh.unwrap_or(1000);
         let handle = IntervalService::spawn(
-            Duration::from_millis(1000 / 60),
-            self.link.callback(|_| Msg::Tick),
+            Duration::from_millis(refresh),
+            self.link.callback(|_| Msg::NextFrame),
         );
-        self.interval_task = Some(handle);
+        self.play_task = Some(handle);
     }
 
-    fn stop_job(&mut self) {
-        self.interval_task.take();
+    fn stop_play(&mut self) {
+        self.play_task.take();
     }
 }
 
@@ -88,11 +141,33 @@ impl Component for App {
     type Properties = ();
 
     fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let config: Config = Config::default();
+        let mut config = match read_hash() {
+            Ok(Some(config)) => config,
+            Ok(None) => Config::default(),
+            Err(_) => {
+                DialogService::alert(
+                    "Invalid configuration in the URL. Using the default configuration.",
+                );
+                Config::default()
+            }
+        };
+        config.period = config.period.max(1);
+        let refresh = match StorageService::new(Area::Local) {
+            Ok(storage) => {
+                let Json(refresh) = storage.restore(REFRESH_KEY);
+                refresh.unwrap_or(Some(DEFAULT_REFRESH))
+            }
+            Err(_) => Some(DEFAULT_REFRESH),
+        };
         let status = Status::Initial;
         let world = INIT_WORLD.to_owned();
         let callback = link.callback(Msg::DataReceived);
-        let worker = Worker::bridge(callback);
+        let mut worker = Worker::bridge(callback);
+        // The worker updates the display while searching.
+        worker.send(Request::SetRefresh(refresh));
+        if config != Config::default() {
+            worker.send(Request::SetWorld(config.clone()));
+        }
 
         App {
             link,
@@ -100,11 +175,21 @@ impl Component for App {
             status,
             gen: 0,
             cells: 0,
+            conflicts: 0,
+            progress: 0.0,
+            results: (None, 0),
             world,
             max_partial: false,
+            auto_continue: false,
+            paint: false,
+            explanation: None,
+            paused: false,
+            refresh,
+            format: OutputFormat::Rle,
             worker,
-            interval_task: None,
+            play_task: None,
             reader_task: None,
+            copied_task: None,
         }
     }
 
@@ -118,7 +203,7 @@ impl Component for App {
                 }
             }
             Msg::IncGen => {
-                if self.gen < self.config.period - 1 {
+                if self.gen < self.last_gen() {
                     self.gen += 1;
                     self.worker.send(Request::DisplayGen(self.gen));
                     return true;
@@ -131,26 +216,72 @@ impl Component for App {
                     return true;
                 }
             }
-            Msg::Start => self.worker.send(Request::Start),
-            Msg::Pause => self.worker.send(Request::Pause),
-            Msg::Reset => self.worker.send(Request::SetWorld(self.config.clone())),
+            Msg::Play => {
+                self.start_play();
+                return true;
+            }
+            Msg::Stop => {
+                self.stop_play();
+                return true;
+            }
+            Msg::NextFrame => {
+                self.gen = (self.gen + 1) % (self.last_gen() + 1);
+                self.worker.send(Request::DisplayGen(self.gen));
+                return true;
+            }
+            Msg::Start => {
+                self.stop_play();
+                self.paused = false;
+                self.worker.send(Request::Start)
+            }
+            Msg::Pause => {
+                self.paused = true;
+                self.worker.send(Request::Pause)
+            }
+            Msg::Reset => {
+                self.stop_play();
+                self.worker.send(Request::SetWorld(self.config.clone()))
+            }
             Msg::Save => self.worker.send(Request::Save),
             Msg::Load(files) => {
-                let file = files.get(0).unwrap();
-                let mut reader_service = ReaderService::new();
-                let task = reader_service
-                    .read_file(file, self.link.callback(Msg::SendFile))
-                    .unwrap();
-                self.reader_task = Some(task)
+                // Dropping the task cancels a previous load that is still pending.
+                self.reader_task = None;
+                // The file list is empty if the dialog is dismissed.
+                if let Some(file) = files.get(0) {
+                    self.stop_play();
+                    let mut reader_service = ReaderService::new();
+                    match reader_service.read_file(file, self.link.callback(Msg::SendFile)) {
+                        Ok(task) => self.reader_task = Some(task),
+                        Err(error) => {
+                            DialogService::alert(&format!("Unable to read the file: {}.", error))
+                        }
+                    }
+                }
             }
             Msg::SendFile(data) => {
-                if let Json(Ok(world_ser)) = Ok(data.content).into() {
-                    self.worker.send(Request::Load(world_ser));
+                self.reader_task = None;
+                // Saved files are JSON objects; anything else is read as RLE.
+                let is_json = data.content.iter().find(|c| !c.is_ascii_whitespace()) == Some(&b'{');
+                if is_json {
+                    if let Json(Ok(world_ser)) = Ok(data.content).into() {
+                        self.worker.send(Request::Load(world_ser));
+                    } else {
+                        DialogService::alert("Broken saved file.");
+                    }
                 } else {
-                    DialogService::alert("Broken saved file.");
+                    let config = String::from_utf8(data.content)
+                        .map_err(|_| "not a text file".to_owned())
+                        .and_then(|rle| Config::from_rle(&rle));
+                    match config {
+                        Ok(config) => self.link.send_message(Msg::Apply(config)),
+                        Err(error) => {
+                            DialogService::alert(&format!("Unable to read the RLE: {}.", error))
+                        }
+                    }
                 }
             }
             Msg::SetMaxPartial => {
+                self.stop_play();
                 self.max_partial ^= true;
                 if self.max_partial {
                     self.worker.send(Request::MaxPartial)
@@ -159,28 +290,79 @@ impl Component for App {
                 }
                 return true;
             }
-            Msg::Apply(config) => {
+            Msg::SetAutoContinue => {
+                self.auto_continue ^= true;
+                return true;
+            }
+            Msg::SetPaint => {
+                self.paint ^= true;
+                return true;
+            }
+            Msg::Paint(((x, y), state)) => {
+                self.worker.send(Request::SetCell((x, y, self.gen), state))
+            }
+            Msg::Explain((x, y)) => self.worker.send(Request::ExplainCell((x, y, self.gen))),
+            Msg::SetRefresh(refresh) => {
+                self.refresh = refresh;
+                if let Ok(mut storage) = StorageService::new(Area::Local) {
+                    storage.store(REFRESH_KEY, Json(&self.refresh));
+                }
+                self.worker.send(Request::SetRefresh(refresh));
+                if self.play_task.is_some() {
+                    self.start_play();
+                }
+                return true;
+            }
+            Msg::SetFormat(format) => self.format = format,
+            Msg::Copy => self.worker.send(Request::Export(self.gen, self.format)),
+            Msg::PrevResult => {
+                self.gen = 0;
+                self.worker.send(Request::PrevResult);
+            }
+            Msg::NextResult => {
+                self.gen = 0;
+                self.worker.send(Request::NextResult);
+            }
+            Msg::HideCopied => {
+                self.copied_task = None;
+                return true;
+            }
+            Msg::Apply(mut config) => {
+                self.stop_play();
+                config.period = config.period.max(1);
                 self.config = config;
+                write_hash(&self.config).ok();
                 self.gen = 0;
                 self.worker.send(Request::SetWorld(self.config.clone()));
                 return true;
             }
             Msg::DataReceived(response) => {
                 match response {
-                    Response::UpdateWorld((world, cells)) => {
+                    Response::UpdateWorld((world, cells, conflicts, progress)) => {
                         self.world = world;
                         self.cells = cells;
+                        self.conflicts = conflicts;
+                        self.progress = progress;
+                        self.explanation = None;
+                    }
+                    Response::CellExplanation(explanation) => {
+                        self.explanation = Some(explanation);
                     }
                     Response::UpdateConfig(config) => {
                         self.config = config;
+                        let gen = self.gen.min(self.last_gen());
+                        if gen != self.gen {
+                            self.gen = gen;
+                            self.worker.send(Request::DisplayGen(self.gen));
+                        }
                     }
                     Response::UpdateStatus(status) => {
-                        let old_status = self.status;
                         if self.status != status {
-                            match (old_status, status) {
-                                (Status::Searching, _) => self.stop_job(),
-                                (_, Status::Searching) => self.start_job(),
-                                _ => (),
+                            if self.status == Status::Searching {
+                                self.link.send_message(Msg::Tick);
+                                if status == Status::Found && self.auto_continue && !self.paused {
+                                    self.link.send_message(Msg::Start);
+                                }
                             }
                             self.status = status;
                         }
@@ -192,6 +374,20 @@ impl Component for App {
                         let text: Text = Json(&world_ser).into();
                         download(&text.unwrap(), "save.json", "application/json").unwrap();
                     }
+                    Response::UpdateResults(results) => {
+                        self.results = results;
+                    }
+                    Response::Exported(text) => {
+                        if copy_to_clipboard(&text).is_ok() {
+                            let handle = TimeoutService::spawn(
+                                Duration::from_secs(2),
+                                self.link.callback(|_| Msg::HideCopied),
+                            );
+                            self.copied_task = Some(handle);
+                        } else {
+                            DialogService::alert("Unable to copy to the clipboard.");
+                        }
+                    }
                 };
                 return true;
             }
@@ -272,13 +468,15 @@ impl App {
                                     </a>
                                 </li>
                                 <li>
-                                    <a data-mui-toggle="tab" data-mui-controls="pane-settings">
+                                    <a data-mui-toggle="tab" data-mui-controls="pane-settings"
+                                        onclick=self.link.callback(|_| Msg::Stop)>
                                         <i class="fas fa-cog"></i>
                                         <span class="mui--hidden-xs"> { "Settings" } </span>
                                     </a>
                                 </li>
                                 <li>
-                                    <a data-mui-toggle="tab" data-mui-controls="pane-help">
+                                    <a data-mui-toggle="tab" data-mui-controls="pane-help"
+                                        onclick=self.link.callback(|_| Msg::Stop)>
                                         <i class="fas fa-question-circle"></i>
                                         <span class="mui--hidden-xs"> { "Help" } </span>
                                     </a>
@@ -297,12 +495,40 @@ impl App {
                                         </abbr>
                                     </label>
                                 </div>
-                                <World world=&self.world/>
+                                <div class="mui-checkbox">
+                                    <label>
+                                        <input id="auto-continue"
+                                            type="checkbox"
+                                            checked=self.auto_continue
+                                            onclick=self.link.callback(|_| Msg::SetAutoContinue)/>
+                                        <abbr title="Continue the search after a result is found, \
+                                            instead of stopping there.">
+                                            { "Auto Continue" }
+                                        </abbr>
+                                    </label>
+                                </div>
+                                <div class="mui-checkbox">
+                                    <label>
+                                        <input id="paint"
+                                            type="checkbox"
+                                            checked=self.paint
+                                            onclick=self.link.callback(|_| Msg::SetPaint)/>
+                                        <abbr title="Click a cell to make it alive, dead, \
+                                            or unknown again, before starting the search.">
+                                            { "Paint" }
+                                        </abbr>
+                                    </label>
+                                </div>
+                                <World world=&self.world
+                                    onpaint=self.onpaint()
+                                    onexplain=self.onexplain()/>
                                 { self.buttons() }
                             </div>
                             <div class="mui-tabs__pane" id="pane-settings">
                                 <Settings config=&self.config
-                                    callback=self.link.callback(Msg::Apply)/>
+                                    refresh=self.refresh
+                                    callback=self.link.callback(Msg::Apply)
+                                    refresh_callback=self.link.callback(Msg::SetRefresh)/>
                             </div>
                             <div class="mui-tabs__pane" id="pane-help">
                                 <Help/>
@@ -314,6 +540,26 @@ impl App {
         }
     }
 
+    /// The callback for painting cells in the world,
+    /// or `None` if the cells cannot be painted now.
+    fn onpaint(&self) -> Option<Callback<Paint>> {
+        if self.paint && !self.max_partial && self.status != Status::Searching {
+            Some(self.link.callback(Msg::Paint))
+        } else {
+            None
+        }
+    }
+
+    /// The callback for explaining the cell under the mouse,
+    /// or `None` if the displayed world is changing.
+    fn onexplain(&self) -> Option<Callback<(isize, isize)>> {
+        if !self.max_partial && self.status != Status::Searching {
+            Some(self.link.callback(Msg::Explain))
+        } else {
+            None
+        }
+    }
+
     fn data(&self) -> Html {
         let onwheel = self.link.callback(|e: WheelEvent| {
             e.prevent_default();
@@ -338,10 +584,11 @@ impl App {
                         <i class="fas fa-minus"></i>
                     </button>
                     <button class="mui-btn mui-btn--small btn-tiny"
-                        disabled=self.gen == self.config.period - 1
+                        disabled=(self.gen >= self.last_gen())
                         onclick=self.link.callback(|_| Msg::IncGen)>
                         <i class="fas fa-plus"></i>
                     </button>
+                    { self.play_button() }
                 </li>
                 <li>
                     <abbr title="Number of known living cells in the current generation. \
@@ -351,14 +598,43 @@ impl App {
                     { ": " }
                     { self.cells }
                 </li>
+                <li>
+                    <abbr title="Number of conflicts during the search.">
+                        { "Conflicts" }
+                    </abbr>
+                    { ": " }
+                    { self.conflicts }
+                </li>
+                <li>
+                    <abbr title="A rough estimate of the explored part of the search space. \
+                        It may jump when the search backtracks.">
+                        { "Progress" }
+                    </abbr>
+                    { ": " }
+                    { format!("{:.2}%", self.progress * 100.0) }
+                </li>
+                <li class=if self.results.1 == 0 { "mui--hide" } else { "" }>
+                    <abbr title="The displayed result and the number of results found.">
+                        { "Result" }
+                    </abbr>
+                    { ": " }
+                    { self.results.0.map_or(0, |i| i + 1) }
+                    { " / " }
+                    { self.results.1 }
+                </li>
+                <li class=if self.explanation.is_some() { "" } else { "mui--hide" }>
+                    { self.explanation.map_or_else(String::new, explain) }
+                </li>
+                <li class=if self.copied_task.is_some() { "" } else { "mui--hide" }>
+                    { "Copied!" }
+                </li>
                 <li>
                     {
-                        match self.status {
-                            Status::Initial => "",
-                            Status::Found => "Found a result.",
-                            Status::None => "No more result.",
-                            Status::Searching => "Searching...",
-                            Status::Paused => "Paused.",
+                        // The web app shows nothing before the first search.
+                        if self.status == Status::Initial {
+                            String::new()
+                        } else {
+                            self.status.to_string()
                         }
                     }
                 </li>
@@ -396,6 +672,19 @@ impl App {
                     </span>
                 </button>
                 <div class="mui--visible-xs-block"></div>
+                { self.result_buttons() }
+                <div class="mui--visible-xs-block"></div>
+                <button class="mui-btn mui-btn--raised"
+                    onclick=self.link.callback(|_| Msg::Copy)>
+                    <i class="fas fa-copy"></i>
+                    <span class="mui--hidden-xs">
+                        <abbr title="Copy the displayed generation to the clipboard \
+                            in the chosen format.">
+                            { "Copy" }
+                        </abbr>
+                    </span>
+                </button>
+                { self.format_select() }
                 <button class="mui-btn mui-btn--raised"
                     disabled=self.status == Status::Searching
                     onclick=self.link.callback(|_| Msg::Save)>
@@ -413,7 +702,8 @@ impl App {
                     })>
                     <i class="fas fa-file-import"></i>
                     <span class="mui--hidden-xs">
-                        <abbr title="Load the search status from a json file.">
+                        <abbr title="Load the search status from a json file, \
+                            or start a new search from a pattern in an RLE file.">
                             { "Load" }
                         </abbr>
                     </span>
@@ -428,6 +718,114 @@ impl App {
             </div>
         }
     }
+
+    fn format_select(&self) -> Html {
+        let formats = [
+            (OutputFormat::Rle, "RLE"),
+            (OutputFormat::RleTrimmed, "RLE (trimmed)"),
+            (OutputFormat::Plaintext, "Plaintext"),
+            (OutputFormat::Macrocell, "Macrocell"),
+        ];
+        let onchange = self.link.callback(move |e: ChangeData| {
+            if let ChangeData::Select(s) = e {
+                formats
+                    .iter()
+                    .find(|(_, name)| *name == s.value())
+                    .map_or(Msg::None, |&(format, _)| Msg::SetFormat(format))
+            } else {
+                Msg::None
+            }
+        });
+        html! {
+            <select id="export_format" onchange=onchange>
+                { for formats.iter().map(|&(format, name)| html! {
+                    <option selected=self.format == format>{ name }</option>
+                }) }
+            </select>
+        }
+    }
+
+    fn play_button(&self) -> Html {
+        if self.play_task.is_some() {
+            html! {
+                <button class="mui-btn mui-btn--small btn-tiny"
+                    onclick=self.link.callback(|_| Msg::Stop)>
+                    <abbr title="Stop playing.">
+                        <i class="fas fa-stop"></i>
+                    </abbr>
+                </button>
+            }
+        } else {
+            html! {
+                <button class="mui-btn mui-btn--small btn-tiny"
+                    disabled=self.status == Status::Searching || self.config.period == 1
+                    onclick=self.link.callback(|_| Msg::Play)>
+                    <abbr title="Cycle through all generations, at the refresh rate in the settings.">
+                        <i class="fas fa-play"></i>
+                    </abbr>
+                </button>
+            }
+        }
+    }
+
+    fn result_buttons(&self) -> Html {
+        let (index, count) = self.results;
+        let searching = self.status == Status::Searching;
+        let no_prev = searching || index.unwrap_or(0) == 0;
+        let no_next = searching || index.map_or(0, |i| i + 1) == count;
+        html! {
+            <>
+                <button class="mui-btn mui-btn--raised"
+                    disabled=no_prev
+                    onclick=self.link.callback(|_| Msg::PrevResult)>
+                    <i class="fas fa-step-backward"></i>
+                    <span class="mui--hidden-xs">
+                        <abbr title="Show the previous result.">
+                            { "Previous" }
+                        </abbr>
+                    </span>
+                </button>
+                <button class="mui-btn mui-btn--raised"
+                    disabled=no_next
+                    onclick=self.link.callback(|_| Msg::NextResult)>
+                    <i class="fas fa-step-forward"></i>
+                    <span class="mui--hidden-xs">
+                        <abbr title="Show the next result.">
+                            { "Next" }
+                        </abbr>
+                    </span>
+                </button>
+            </>
+        }
+    }
+}
+
+/// Reads the configuration from the hash of the URL,
+/// which is the base64 encoding of the configuration in JSON.
+///
+/// Returns `Ok(None)` if the hash is empty.
+fn read_hash() -> Result<Option<Config>, JsValue> {
+    let window = web_sys::window().ok_or(JsValue::UNDEFINED)?;
+    let hash = window.location().hash()?;
+    let hash = hash.trim_start_matches('#');
+    if hash.is_empty() {
+        return Ok(None);
+    }
+    let text: Text = Ok(window.atob(hash)?);
+    let Json(config) = text.into();
+    config.map(Some).map_err(|_| JsValue::UNDEFINED)
+}
+
+/// Writes the configuration to the hash of the URL without reloading,
+/// so that the URL can be shared.
+fn write_hash(config: &Config) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or(JsValue::UNDEFINED)?;
+    let text: Text = Json(config).into();
+    let json = text.map_err(|_| JsValue::UNDEFINED)?;
+    let hash = format!("#{}", window.btoa(&json)?);
+    window
+        .history()?
+        .replace_state_with_url(&JsValue::NULL, "", Some(&hash))
 }
 
 fn download(text: &str, name: &str, mime: &str) -> Result<(), JsValue> {
@@ -450,6 +848,15 @@ fn download(text: &str, name: &str, mime: &str) -> Result<(), JsValue> {
     Url::revoke_object_url(&a.href())
 }
 
+/// Writes the text to the clipboard with `navigator.clipboard.writeText`.
+fn copy_to_clipboard(text: &str) -> Result<(), JsValue> {
+    let navigator = web_sys::window().ok_or(JsValue::UNDEFINED)?.navigator();
+    let clipboard = Reflect::get(&navigator, &JsValue::from_str("clipboard"))?;
+    let write_text = Function::from(Reflect::get(&clipboard, &JsValue::from_str("writeText"))?);
+    write_text.call1(&clipboard, &JsValue::from_str(text))?;
+    Ok(())
+}
+
 fn click_button(id: &str) -> Result<(), JsValue> {
     let button = HtmlElement::from(JsValue::from(
         web_sys::window()
@@ -462,3 +869,22 @@ fn click_button(id: &str) -> Result<(), JsValue> {
     button.click();
     Ok(())
 }
+
+/// Describes a cell, its state, and why it has this state.
+fn explain(((x, y, _), state, reason): Explanation) -> String {
+    let state = match state {
+        None => "unknown".to_owned(),
+        Some(DEAD) => "dead".to_owned(),
+        Some(ALIVE) => "alive".to_owned(),
+        Some(State(i)) => format!("dying ({})", i),
+    };
+    let reason = match reason {
+        None => "",
+        Some(SetReason::Init) => ", fixed by the settings",
+        Some(SetReason::Known) => ", given as a known cell",
+        Some(SetReason::Assume) => ", chosen by the search",
+        Some(SetReason::Deduce) => ", deduced from other cells",
+        Some(SetReason::Conflict) => ", since other states led to conflicts",
+    };
+    format!("Cell ({}, {}): {}{}", x, y, state, reason)
+}
@@ -1,4 +1,6 @@
-use rlifesrc_lib::{rules::NtLifeGen, Config, NewState, SearchOrder, Symmetry, Transform};
+use rlifesrc_lib::{
+    rules::validate_rule_string, Config, NewState, SearchOrder, Symmetry, Transform,
+};
 use wasm_bindgen::prelude::wasm_bindgen;
 use yew::{
     html, html::ChangeData, Callback, Component, ComponentLink, Html, Properties, ShouldRender,
@@ -13,14 +15,18 @@ extern "C" {
 pub struct Settings {
     link: ComponentLink<Self>,
     callback: Callback<Config>,
+    refresh_callback: Callback<Option<u64>>,
     config: Config,
-    rule_is_valid: bool,
+    refresh: Option<u64>,
+    rule_error: Option<String>,
 }
 
 #[derive(Clone, Properties)]
 pub struct Props {
     pub config: Config,
+    pub refresh: Option<u64>,
     pub callback: Callback<Config>,
+    pub refresh_callback: Callback<Option<u64>>,
 }
 
 pub enum Msg {
@@ -38,20 +44,32 @@ pub enum Msg {
     SetMax(Option<usize>),
     SetFront,
     SetReduce,
+    SetStrict,
+    SetRefresh(Option<u64>),
+    SetPreset(String),
     None,
 }
 
+/// Why the rule string cannot be parsed as a supported rule, if it cannot.
+fn rule_error(rule_string: &str) -> Option<String> {
+    validate_rule_string(rule_string)
+        .err()
+        .map(|e| e.to_string())
+}
+
 impl Component for Settings {
     type Message = Msg;
     type Properties = Props;
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let rule_is_valid = props.config.rule_string.parse::<NtLifeGen>().is_ok();
+        let rule_error = rule_error(&props.config.rule_string);
         Settings {
             link,
             callback: props.callback,
+            refresh_callback: props.refresh_callback,
             config: props.config,
-            rule_is_valid,
+            refresh: props.refresh,
+            rule_error,
         }
     }
 
@@ -81,7 +99,7 @@ impl Component for Settings {
             Msg::SetTrans(transform) => self.config.transform = transform,
             Msg::SetSym(symmetry) => self.config.symmetry = symmetry,
             Msg::SetRule(rule_string) => {
-                self.rule_is_valid = rule_string.parse::<NtLifeGen>().is_ok();
+                self.rule_error = rule_error(&rule_string);
                 self.config.rule_string = rule_string;
             }
             Msg::SetOrder(search_order) => self.config.search_order = search_order,
@@ -89,6 +107,18 @@ impl Component for Settings {
             Msg::SetMax(max_cell_count) => self.config.max_cell_count = max_cell_count,
             Msg::SetFront => self.config.non_empty_front ^= true,
             Msg::SetReduce => self.config.reduce_max ^= true,
+            Msg::SetStrict => self.config.strict_period ^= true,
+            Msg::SetPreset(name) => match Config::preset(&name) {
+                Some(config) => {
+                    self.rule_error = rule_error(&config.rule_string);
+                    self.config = config;
+                }
+                None => return false,
+            },
+            Msg::SetRefresh(refresh) => {
+                self.refresh_callback.emit(refresh);
+                return false;
+            }
             Msg::Apply => {
                 self.callback.emit(self.config.clone());
                 return false;
@@ -99,11 +129,13 @@ impl Component for Settings {
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let refresh_changed = self.refresh != props.refresh;
+        self.refresh = props.refresh;
         self.config != props.config && {
             self.config = props.config;
-            self.rule_is_valid = self.config.rule_string.parse::<NtLifeGen>().is_ok();
+            self.rule_error = rule_error(&self.config.rule_string);
             true
-        }
+        } || refresh_changed
     }
 
     fn view(&self) -> Html {
@@ -141,6 +173,7 @@ impl Settings {
     fn settings(&self) -> Html {
         html! {
             <div id="settings">
+                { self.set_preset() }
                 { self.set_rule() }
                 { self.set_width() }
                 { self.set_height() }
@@ -154,6 +187,33 @@ impl Settings {
                 { self.set_choose() }
                 { self.set_front() }
                 { self.set_reduce() }
+                { self.set_strict() }
+                { self.set_refresh() }
+            </div>
+        }
+    }
+
+    fn set_preset(&self) -> Html {
+        let onchange = self.link.callback(|e: ChangeData| {
+            if let ChangeData::Select(s) = e {
+                Msg::SetPreset(s.value())
+            } else {
+                Msg::None
+            }
+        });
+        html! {
+            <div class="mui-select">
+                <label for="set_preset">
+                    <abbr title="Replace all the settings below with a preset \
+                        for Conway's Game of Life.">
+                        { "Preset" }
+                    </abbr>
+                    { ":" }
+                </label>
+                <select id="set_preset" onchange=onchange>
+                    <option selected=true> { "Custom" } </option>
+                    { for Config::PRESETS.iter().map(|&name| html! { <option> { name } </option> }) }
+                </select>
             </div>
         }
     }
@@ -178,9 +238,20 @@ impl Settings {
                 </label>
                 <input id="set_rule"
                     type="text"
-                    class=if self.rule_is_valid { "" } else { "mui--is-invalid" }
+                    class=if self.rule_error.is_none() { "" } else { "mui--is-invalid" }
                     value=self.config.rule_string.clone()
                     onchange=onchange/>
+                {
+                    if let Some(error) = &self.rule_error {
+                        html! {
+                            <span class="mui--text-danger mui--text-caption">
+                                { error }
+                            </span>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
             </div>
         }
     }
@@ -378,6 +449,23 @@ impl Settings {
         }
     }
 
+    fn set_strict(&self) -> Html {
+        html! {
+            <div class="mui-checkbox">
+                <label>
+                    <input id="set_strict"
+                        type="checkbox"
+                        checked=self.config.strict_period
+                        onclick=self.link.callback(|_| Msg::SetStrict)/>
+                    <abbr title="Reject patterns whose actual period is smaller than the given period, \
+                        taking rotations, reflections and translations into account.">
+                        { "Strict period" }
+                    </abbr>
+                </label>
+            </div>
+        }
+    }
+
     fn set_trans(&self) -> Html {
         let onchange = self.link.callback(|e: ChangeData| {
             if let ChangeData::Select(s) = e {
@@ -508,7 +596,9 @@ impl Settings {
                     { ":" }
                 </label>
                 <select id="set_order" onchange=onchange>
-                    <option> { "Automatic" } </option>
+                    <option value="Automatic">
+                        { format!("Automatic ({})", order_name(self.config.auto_search_order())) }
+                    </option>
                     <option value="Column"> { "Column first" } </option>
                     <option value="Row"> { "Row first" } </option>
                     <option value="Diagonal" disabled=self.config.width != self.config.height>
@@ -548,4 +638,46 @@ impl Settings {
             </div>
         }
     }
+
+    fn set_refresh(&self) -> Html {
+        let onchange = self.link.callback(|e: ChangeData| {
+            if let ChangeData::Select(s) = e {
+                match s.value().as_ref() {
+                    "Manual" => Msg::SetRefresh(None),
+                    v => v.parse().map_or(Msg::None, |v| Msg::SetRefresh(Some(v))),
+                }
+            } else {
+                Msg::None
+            }
+        });
+        html! {
+            <div class="mui-select">
+                <label for="set_refresh">
+                    <abbr title="How often the displayed world is refreshed during the search.\n\
+                        A lower refresh rate uses less CPU. In manual mode, the world is only \
+                        refreshed when the search stops or the displayed generation changes.\n\
+                        This takes effect immediately, without applying the settings.">
+                        { "Refresh rate" }
+                    </abbr>
+                    { ":" }
+                </label>
+                <select id="set_refresh" onchange=onchange>
+                    <option value="16" selected=self.refresh == Some(16)> { "60 fps" } </option>
+                    <option value="33" selected=self.refresh == Some(33)> { "30 fps" } </option>
+                    <option value="100" selected=self.refresh == Some(100)> { "10 fps" } </option>
+                    <option value="1000" selected=self.refresh == Some(1000)> { "1 fps" } </option>
+                    <option value="Manual" selected=self.refresh.is_none()> { "Manual" } </option>
+                </select>
+            </div>
+        }
+    }
+}
+
+/// The name of a search order, as shown in the search order option.
+fn order_name(search_order: SearchOrder) -> &'static str {
+    match search_order {
+        SearchOrder::ColumnFirst => "Column first",
+        SearchOrder::RowFirst => "Row first",
+        SearchOrder::Diagonal => "Diagonal",
+    }
 }
@@ -1,6 +1,7 @@
-use rlifesrc_lib::{Config, Search, Status, WorldSer};
+use js_sys::Date;
+use rlifesrc_lib::{Config, Error, OutputFormat, Search, SetReason, State, Status, WorldSer};
 use serde::{Deserialize, Serialize};
-use std::{option_env, time::Duration};
+use std::{cell::Cell, option_env, rc::Rc, sync::atomic::Ordering, time::Duration};
 use yew::{
     agent::{Agent, AgentLink, HandlerId, Public},
     services::timeout::{TimeoutService, TimeoutTask},
@@ -8,30 +9,61 @@ use yew::{
 
 const VIEW_FREQ: u64 = 50000;
 
+/// How often the progress callback checks whether the display
+/// should be updated, in number of conflicts.
+const REFRESH_CONFLICTS: u64 = 1000;
+
+/// A cell, its state, and why it has this state.
+pub type Explanation = ((isize, isize, isize), Option<State>, Option<SetReason>);
+
 #[derive(Serialize, Deserialize)]
 pub enum Request {
     Start,
     Pause,
     SetWorld(Config),
+    /// Sets a cell to the given state, or makes it unknown if the state is `None`.
+    SetCell((isize, isize, isize), Option<State>),
     DisplayGen(isize),
+    /// Sets the interval in milliseconds between the updates of the display
+    /// that are sent while searching, or `None` to send no such updates.
+    SetRefresh(Option<u64>),
+    /// Asks for the state of a cell and why it has this state.
+    ExplainCell((isize, isize, isize)),
     MaxPartial,
     Save,
     Load(WorldSer),
+    /// Exports a generation in the given format.
+    Export(isize, OutputFormat),
+    PrevResult,
+    NextResult,
 }
 
 #[derive(Serialize, Deserialize)]
 pub enum Response {
-    UpdateWorld((String, usize)),
+    /// The world, the number of living cells, the number of conflicts,
+    /// and the estimated progress.
+    UpdateWorld((String, usize, u64, f64)),
     UpdateStatus(Status),
     UpdateConfig(Config),
     Error(String),
     Save(WorldSer),
+    Exported(String),
+    /// The index of the displayed result and the number of results found.
+    UpdateResults((Option<usize>, usize)),
+    /// A cell, its state, and why it has this state.
+    CellExplanation(Explanation),
 }
 
 pub enum WorkerMsg {
     Step,
 }
 
+/// Whether `refresh` milliseconds have passed since the last update
+/// of the display at `last_refresh`.
+fn refresh_due(refresh: Option<u64>, last_refresh: f64) -> bool {
+    matches!(refresh, Some(refresh) if Date::now() - last_refresh >= refresh as f64)
+}
+
 pub struct Worker {
     status: Status,
     search: Box<dyn Search>,
@@ -39,6 +71,20 @@ pub struct Worker {
     max_partial: String,
     link: AgentLink<Worker>,
     timeout_task: Option<TimeoutTask>,
+    /// The handler that started the search, to be told when it stops.
+    handler: Option<HandlerId>,
+    /// Snapshots of the results found so far.
+    results: Vec<WorldSer>,
+    /// Index of the result in `results` that the current search starts from.
+    result_index: Option<usize>,
+    /// The displayed generation, or `None` if the maximal partial result
+    /// is displayed.
+    view: Option<isize>,
+    /// Interval in milliseconds between the updates of the display
+    /// while searching. See `Request::SetRefresh`.
+    refresh: Rc<Cell<Option<u64>>>,
+    /// The time of the last update of the display, from `Date::now`.
+    last_refresh: Rc<Cell<f64>>,
 }
 
 impl Worker {
@@ -61,17 +107,136 @@ impl Worker {
             .unwrap();
         if !check_max || cell_count > self.max_partial_count {
             self.max_partial_count = cell_count;
-            self.max_partial = self.search.rle_gen(gen);
+            self.max_partial = self.search.max_partial(gen, true);
         }
     }
 
+    /// Lets the progress callback interrupt the search when the display
+    /// should be updated, so that slow searches do not look frozen.
+    ///
+    /// The interrupted search returns `Status::Paused`.
+    fn set_refresh_callback(&mut self) {
+        let refresh = self.refresh.clone();
+        let last_refresh = self.last_refresh.clone();
+        let abort = self.search.abort_handle();
+        self.search.set_progress_callback(
+            REFRESH_CONFLICTS,
+            Box::new(move |_| {
+                if refresh_due(refresh.get(), last_refresh.get()) {
+                    abort.store(true, Ordering::Relaxed);
+                }
+            }),
+        );
+    }
+
+    /// Sends the displayed generation or the maximal partial result,
+    /// if the last update of the display is older than `refresh`.
+    fn refresh(&mut self, id: HandlerId) {
+        if !refresh_due(self.refresh.get(), self.last_refresh.get()) {
+            return;
+        }
+        match self.view {
+            Some(gen) => self.update_world(id, gen),
+            None => self.update_max_partial(id),
+        }
+    }
+
+    fn update_max_partial(&mut self, id: HandlerId) {
+        self.last_refresh.set(Date::now());
+        self.link.respond(
+            id,
+            Response::UpdateWorld((
+                self.max_partial.clone(),
+                self.max_partial_count,
+                self.search.conflicts(),
+                self.search.progress(),
+            )),
+        );
+        self.update_status(id);
+    }
+
     fn update_world(&mut self, id: HandlerId, gen: isize) {
+        self.last_refresh.set(Date::now());
         let world = self.search.rle_gen(gen);
         let count = self.search.cell_count_gen(gen);
-        self.link.respond(id, Response::UpdateWorld((world, count)));
+        let conflicts = self.search.conflicts();
+        let progress = self.search.progress();
+        self.link.respond(
+            id,
+            Response::UpdateWorld((world, count, conflicts, progress)),
+        );
         self.update_status(id);
     }
 
+    fn update_results(&mut self, id: HandlerId) {
+        self.link.respond(
+            id,
+            Response::UpdateResults((self.result_index, self.results.len())),
+        );
+    }
+
+    fn clear_results(&mut self, id: HandlerId) {
+        self.results.clear();
+        self.result_index = None;
+        self.update_results(id);
+    }
+
+    /// Saves a snapshot of the current result.
+    ///
+    /// Results after the current one are dropped, as they will be found again.
+    fn push_result(&mut self) {
+        let len = self.result_index.map_or(0, |i| i + 1);
+        self.results.truncate(len);
+        self.results.push(self.search.ser());
+        self.result_index = Some(len);
+    }
+
+    /// Replaces the current search with the snapshot of the `index`-th result.
+    fn show_result(&mut self, id: HandlerId, index: usize) {
+        match self.results[index].world() {
+            Ok(search) => {
+                self.stop_job();
+                self.status = Status::Found;
+                self.search = search;
+                self.result_index = Some(index);
+                self.update_max_martial(false);
+                self.link
+                    .respond(id, Response::UpdateConfig(self.search.config().clone()));
+                self.update_results(id);
+                self.update_world(id, 0);
+            }
+            Err(error) => {
+                let message = error.to_string();
+                self.link.respond(id, Response::Error(message));
+            }
+        }
+    }
+
+    /// Sets a cell to the given state, or makes it unknown.
+    ///
+    /// Unknown cells are set directly in the current search.
+    /// Otherwise the world is built again with the new known cells.
+    fn set_cell(
+        &mut self,
+        coord: (isize, isize, isize),
+        state: Option<State>,
+    ) -> Result<(), Error> {
+        if let (Some(state), Ok(None), false) = (
+            state,
+            self.search.get_cell_state(coord),
+            self.status == Status::None,
+        ) {
+            return self.search.try_set_cell(coord, state);
+        }
+        let mut config = self.search.config().clone();
+        config.known_cells.retain(|&(c, _)| c != coord);
+        if let Some(state) = state {
+            config.known_cells.push((coord, state));
+        }
+        self.search = config.world()?;
+        Ok(())
+    }
+
     fn update_status(&mut self, id: HandlerId) {
         let status = self.status;
         if Status::Found == status && self.search.config().reduce_max {
@@ -99,6 +264,12 @@ impl Agent for Worker {
             max_partial: String::new(),
             link,
             timeout_task: None,
+            handler: None,
+            results: Vec::new(),
+            result_index: None,
+            view: Some(0),
+            refresh: Rc::new(Cell::new(None)),
+            last_refresh: Rc::new(Cell::new(0.0)),
         };
         worker.update_max_martial(false);
         worker
@@ -108,9 +279,26 @@ impl Agent for Worker {
         match msg {
             WorkerMsg::Step => {
                 if let Status::Searching = self.status {
-                    self.status = self.search.search(Some(VIEW_FREQ));
+                    self.status = match self.search.search(Some(VIEW_FREQ)) {
+                        // Interrupted by the callback set in `set_refresh_callback`.
+                        Status::Paused => Status::Searching,
+                        status => status,
+                    };
                     self.update_max_martial(true);
-                    self.start_job();
+                    if let Status::Searching = self.status {
+                        if let Some(id) = self.handler {
+                            self.refresh(id);
+                        }
+                        self.start_job();
+                    } else {
+                        if let Status::Found = self.status {
+                            self.push_result();
+                        }
+                        if let Some(id) = self.handler.take() {
+                            self.update_results(id);
+                            self.update_status(id);
+                        }
+                    }
                 } else {
                     self.stop_job();
                 }
@@ -121,7 +309,9 @@ impl Agent for Worker {
     fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
         match msg {
             Request::Start => {
+                self.handler = Some(id);
                 self.status = Status::Searching;
+                self.set_refresh_callback();
                 self.update_status(id);
                 self.start_job();
             }
@@ -133,10 +323,22 @@ impl Agent for Worker {
             Request::SetWorld(config) => {
                 self.stop_job();
                 self.status = Status::Initial;
-                match config.world() {
-                    Ok(search) => {
-                        self.search = search;
+                // Resetting the world is faster than creating a new one
+                // if the configuration is not changed.
+                let result = if &config == self.search.config() {
+                    self.search.reset()
+                } else {
+                    config.world().map(|search| self.search = search)
+                };
+                match result {
+                    Ok(()) => {
+                        // Resetting restores the max cell count lowered by `reduce_max`.
+                        if &config != self.search.config() {
+                            self.link
+                                .respond(id, Response::UpdateConfig(self.search.config().clone()));
+                        }
                         self.update_max_martial(false);
+                        self.clear_results(id);
                         self.update_world(id, 0);
                     }
                     Err(error) => {
@@ -145,20 +347,59 @@ impl Agent for Worker {
                     }
                 }
             }
+            Request::SetCell(coord, state) => {
+                self.stop_job();
+                match self.set_cell(coord, state) {
+                    Ok(()) => {
+                        self.status = Status::Initial;
+                        self.update_max_martial(false);
+                        self.link
+                            .respond(id, Response::UpdateConfig(self.search.config().clone()));
+                        self.clear_results(id);
+                        self.update_world(id, coord.2);
+                    }
+                    Err(error) => {
+                        let message = error.to_string();
+                        self.link.respond(id, Response::Error(message));
+                    }
+                }
+            }
             Request::DisplayGen(gen) => {
+                self.view = Some(gen);
                 self.update_world(id, gen);
             }
+            Request::SetRefresh(refresh) => {
+                self.refresh.set(refresh);
+            }
+            Request::ExplainCell(coord) => {
+                let state = self.search.get_cell(coord);
+                let reason = self.search.cell_reason(coord).ok().flatten();
+                self.link
+                    .respond(id, Response::CellExplanation((coord, state, reason)));
+            }
             Request::MaxPartial => {
-                self.link.respond(
-                    id,
-                    Response::UpdateWorld((self.max_partial.clone(), self.max_partial_count)),
-                );
-                self.update_status(id);
+                self.view = None;
+                self.update_max_partial(id);
             }
             Request::Save => {
                 let world_ser = self.search.ser();
                 self.link.respond(id, Response::Save(world_ser));
             }
+            Request::Export(gen, format) => {
+                let text = self.search.export(gen, format);
+                self.link.respond(id, Response::Exported(text));
+            }
+            Request::PrevResult => {
+                if let Some(index) = self.result_index.filter(|&i| i > 0) {
+                    self.show_result(id, index - 1);
+                }
+            }
+            Request::NextResult => {
+                let index = self.result_index.map_or(0, |i| i + 1);
+                if index < self.results.len() {
+                    self.show_result(id, index);
+                }
+            }
             Request::Load(world_ser) => {
                 self.stop_job();
                 self.status = Status::Paused;
@@ -168,6 +409,7 @@ impl Agent for Worker {
                         self.update_max_martial(false);
                         self.link
                             .respond(id, Response::UpdateConfig(self.search.config().clone()));
+                        self.clear_results(id);
                         self.update_world(id, 0);
                     }
                     Err(error) => {
@@ -1,16 +1,29 @@
+use rlifesrc_lib::{State, ALIVE, DEAD};
 use yew::{
-    events::MouseEvent, html, Component, ComponentLink, Html, NodeRef, Properties, ShouldRender,
+    events::MouseEvent, html, Callback, Component, ComponentLink, Html, NodeRef, Properties,
+    ShouldRender,
 };
 
+/// Position of a cell in the displayed generation, and the state to paint it.
+pub type Paint = ((isize, isize), Option<State>);
+
 pub struct World {
     link: ComponentLink<Self>,
     world: String,
+    onpaint: Option<Callback<Paint>>,
+    onexplain: Option<Callback<(isize, isize)>>,
     node_ref: NodeRef,
 }
 
 #[derive(Clone, Properties)]
 pub struct Props {
     pub world: String,
+    /// Called when a cell is clicked. Cells are not clickable if this is `None`.
+    #[prop_or_default]
+    pub onpaint: Option<Callback<Paint>>,
+    /// Called when the mouse moves over a cell.
+    #[prop_or_default]
+    pub onexplain: Option<Callback<(isize, isize)>>,
 }
 
 pub enum Msg {
@@ -25,13 +38,15 @@ impl Component for World {
         World {
             link,
             world: props.world,
+            onpaint: props.onpaint,
+            onexplain: props.onexplain,
             node_ref: NodeRef::default(),
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
         match msg {
-            Msg::Select => {
+            Msg::Select if self.onpaint.is_none() => {
                 if let Some(node) = self.node_ref.get() {
                     if let Ok(Some(selection)) = web_sys::window().unwrap().get_selection() {
                         selection.select_all_children(&node).unwrap();
@@ -39,11 +54,16 @@ impl Component for World {
                 }
                 false
             }
+            Msg::Select => false,
         }
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
-        self.world != props.world && {
+        let callbacks_changed = self.onpaint.is_some() != props.onpaint.is_some()
+            || self.onexplain.is_some() != props.onexplain.is_some();
+        self.onpaint = props.onpaint;
+        self.onexplain = props.onexplain;
+        (self.world != props.world || callbacks_changed) && {
             self.world = props.world;
             true
         }
@@ -54,12 +74,111 @@ impl Component for World {
             e.prevent_default();
             Msg::Select
         });
+        let cells = if self.onpaint.is_some() || self.onexplain.is_some() {
+            self.cells()
+        } else {
+            self.colored()
+        };
         html! {
             <pre id="world"
                 ref=self.node_ref.clone()
                 ondblclick=ondblclick>
-                { &self.world }
+                { for cells }
             </pre>
         }
     }
 }
+
+impl World {
+    /// Splits the world into runs of text, where dying cells of Generations
+    /// rules are colored in a gradient according to their states.
+    ///
+    /// Dying cells are the uppercase letters starting from `B`.
+    /// The header line is left uncolored, since the rule string in it
+    /// also contains uppercase letters.
+    fn colored(&self) -> Vec<Html> {
+        let body_start = self.world.find('\n').map_or(0, |i| i + 1);
+        let (header, body) = self.world.split_at(body_start);
+        let mut nodes = vec![html! { { header } }];
+        let mut run = String::new();
+        // The dying state of the current run, or `None` for other cells.
+        let mut run_state = None;
+        for c in body.chars() {
+            let state = if let 'B'..='Z' = c { Some(c) } else { None };
+            if state != run_state && !run.is_empty() {
+                nodes.push(run_node(&run, run_state));
+                run.clear();
+            }
+            run_state = state;
+            run.push(c);
+        }
+        nodes.push(run_node(&run, run_state));
+        nodes
+    }
+
+    /// Splits the world into single cells, which can be clicked to paint them,
+    /// or hovered over to explain them.
+    ///
+    /// Clicking a cell cycles it through unknown, alive and dead.
+    /// Dying cells of Generations rules become unknown.
+    fn cells(&self) -> Vec<Html> {
+        let body_start = self.world.find('\n').map_or(0, |i| i + 1);
+        let (header, body) = self.world.split_at(body_start);
+        let mut nodes = vec![html! { { header } }];
+        for (y, line) in body.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let '$' | '!' = c {
+                    nodes.push(html! { { c } });
+                    continue;
+                }
+                let state = match c {
+                    '?' => Some(ALIVE),
+                    'o' | 'A' => Some(DEAD),
+                    _ => None,
+                };
+                let coord = (x as isize, y as isize);
+                let onclick = match &self.onpaint {
+                    Some(onpaint) => onpaint.reform(move |_: MouseEvent| (coord, state)),
+                    None => Callback::noop(),
+                };
+                let onmouseover = match &self.onexplain {
+                    Some(onexplain) => onexplain.reform(move |_: MouseEvent| coord),
+                    None => Callback::noop(),
+                };
+                let class = if self.onpaint.is_some() { "cell" } else { "" };
+                let style = dying_style(c).unwrap_or_default();
+                nodes.push(html! {
+                    <span class=class style=style onclick=onclick onmouseover=onmouseover>
+                        { c }
+                    </span>
+                });
+            }
+            nodes.push(html! { { "\n" } });
+        }
+        nodes
+    }
+}
+
+/// The color of a dying cell of Generations rules, or `None` for other cells.
+///
+/// Dying cells are the uppercase letters starting from `B`.
+/// Later states are lighter.
+fn dying_style(c: char) -> Option<String> {
+    if let 'B'..='Z' = c {
+        let lightness = (30 + 10 * (c as u32 - 'B' as u32)).min(80);
+        Some(format!("color: hsl(0, 0%, {}%)", lightness))
+    } else {
+        None
+    }
+}
+
+/// A run of cells of the same dying state, or of cells that are not dying.
+fn run_node(run: &str, state: Option<char>) -> Html {
+    match state {
+        Some(c) => {
+            let style = dying_style(c).unwrap();
+            html! { <span style=style>{ run }</span> }
+        }
+        None => html! { { run } },
+    }
+}